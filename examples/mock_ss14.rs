@@ -0,0 +1,25 @@
+//! Minimal stand-in for SS14 server admin API, prints every request it gets and answers 200.
+//!
+//! Run with `cargo run --example mock_ss14 [host]`, `127.0.0.1:1212` by default,
+//! point `ss14_api_url` to `http://127.0.0.1:1212/admins` and `ss14_status_url` to `http://127.0.0.1:1212/status`.
+
+use axum::{body::Bytes, http::{HeaderMap, Method, Uri}, routing::get, Router};
+
+async fn status() -> &'static str {
+    r#"{"name": "Mock Server", "players": 42, "soft_max_players": 80, "run_level": 1, "round_id": 1234, "preset": "Secret", "round_start_time": "2025-01-01T12:00:00Z"}"#
+}
+
+async fn handle(method: Method, uri: Uri, headers: HeaderMap, body: Bytes) -> &'static str {
+    let actor = headers.get("Actor").and_then(|v| v.to_str().ok()).unwrap_or("none");
+    println!("{} {}\nActor: {}\n{}\n", method, uri, actor, String::from_utf8_lossy(&body));
+    "{}"
+}
+
+#[tokio::main]
+async fn main() {
+    let host = std::env::args().nth(1).unwrap_or("127.0.0.1:1212".to_string());
+    let listener = tokio::net::TcpListener::bind(&host).await.expect("Unable to bind mock server");
+    println!("Mock SS14 server is listening on {}", host);
+
+    axum::serve(listener, Router::new().route("/status", get(status)).fallback(handle)).await.expect("Mock server error");
+}
@@ -1,14 +1,18 @@
-use std::{str::FromStr, time::Duration};
+use std::{collections::HashMap, str::FromStr, time::Duration};
 
+use futures::{stream, StreamExt};
 use log::error;
 use reqwest::Method;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
+use serenity::all::UserId;
 use uuid::Uuid;
 
 use crate::error::Error;
 
 static TIMEOUT: Duration = Duration::from_secs(5);
 static USER_AGENT: &str = "VoidRelay Discord Bot";
+// typeauthd has no bulk lookup, so batches are sent as this many parallel requests
+static BATCH_CONCURRENCY: usize = 8;
 
 pub struct DiscordApiClient {
     inner: reqwest::Client,
@@ -41,8 +45,41 @@ impl DiscordApiClient {
     }
 
     pub async fn uuid(&self, discord_id: &str) -> Option<Uuid> {
+        let response = self.get::<DiscordAuthUuidResponse>(&format!("api/uuid?method=discord&id={}", discord_id)).await?;
+        match Uuid::from_str(&response.uuid) {
+            Ok(uuid) => Some(uuid),
+            Err(e) => {
+                error!("Error occured during parsing auth response UUID to UUID struct. Error: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Same as [`Self::uuid`] for many accounts at once, unlinked accounts are left out.
+    pub async fn uuids(&self, discord_ids: &[UserId]) -> HashMap<UserId, Uuid> {
+        stream::iter(discord_ids.iter().copied())
+            .map(|discord_id| async move { (discord_id, self.uuid(&discord_id.get().to_string()).await) })
+            .buffer_unordered(BATCH_CONCURRENCY)
+            .filter_map(|(discord_id, uuid)| async move { uuid.map(|uuid| (discord_id, uuid)) })
+            .collect()
+            .await
+    }
+
+    /// Reverse of [`Self::uuid`], looks up linked discord account by SS14 UUID.
+    pub async fn discord_id(&self, uuid: &Uuid) -> Option<UserId> {
+        let response = self.get::<DiscordAuthDiscordIdResponse>(&format!("api/discord?method=uuid&id={}", uuid)).await?;
+        match response.discord_id.parse::<u64>() {
+            Ok(id) if id != 0 => Some(UserId::new(id)),
+            _ => {
+                error!("Auth client returned invalid discord id: {}", response.discord_id);
+                None
+            }
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Option<T> {
         let request = self.inner
-            .request(Method::GET, format!("{}/api/uuid?method=discord&id={}", self.api_url, discord_id))
+            .request(Method::GET, format!("{}/{}", self.api_url, path))
             .bearer_auth(&self.api_key)
             .build();
 
@@ -53,34 +90,29 @@ impl DiscordApiClient {
 
         let request = request.unwrap();
 
-        let response = self.inner.execute(request).await;
-        if let Err(e) = &response {
-            if e.is_connect() {
-                error!("Connecting error in auth client. Check if auth service is up.");
+        let response = match self.inner.execute(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                if e.is_connect() {
+                    error!("Connecting error in auth client. Check if auth service is up.");
+                } else {
+                    error!("Error executing auth request: {}", e);
+                }
                 return None;
             }
-        }
-        let response = response.unwrap();
+        };
         let status = response.status();
 
         if status.is_success() {
             if let Ok(bytes) = response.bytes().await {
                 let body = bytes.into_iter().collect::<Vec<_>>();
-                let uuid = serde_json::from_slice::<DiscordAuthUuidResponse>(&body);
-                if let Err(e) = uuid {
-                    error!("Error deserializing UUID response from auth client. Error: {}", e);
-                    return None;
-                } else {
-                    let uuid = uuid.unwrap().uuid;
-                    let uuid = Uuid::from_str(&uuid);
-                    match uuid {
-                        Ok(uuid) => return Some(uuid),
-                        Err(e) => {
-                            error!("Error occured during parsing auth response UUID to UUID struct. Error: {}", e);
-                            return None;
-                        }
+                return match serde_json::from_slice::<T>(&body) {
+                    Ok(value) => Some(value),
+                    Err(e) => {
+                        error!("Error deserializing response from auth client. Error: {}", e);
+                        None
                     }
-                }
+                };
             } else {
                 error!("Request has been successfully proceed, but response is empty.");
                 return None;
@@ -94,7 +126,7 @@ impl DiscordApiClient {
                 error!("Error deserializing error response from auth client. Error: {}", e);
             } else {
                 let err = err_response.unwrap();
-                error!("Error occured during fetching UUID from auth client. Error: {}", err.error);
+                error!("Error occured during fetching data from auth client. Error: {}", err.error);
             }
         }
 
@@ -112,4 +144,9 @@ pub struct ErrorResponse {
 #[derive(Debug, Deserialize)]
 pub struct DiscordAuthUuidResponse {
     pub uuid: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiscordAuthDiscordIdResponse {
+    pub discord_id: String,
 }
\ No newline at end of file
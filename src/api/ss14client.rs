@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use reqwest::{self, Method};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use sqlx::types::ipnetwork::IpNetwork;
 use uuid::Uuid;
 
 use crate::error::Error;
@@ -40,10 +42,90 @@ impl SS14ApiClient {
     }
 
     pub async fn pardon(&self, req: PardonRequest) -> Result<(), Error> {
-        let actor = serde_json::to_string(&req.actor)?;
-        let body = serde_json::to_string(&req)?;
+        self.post_action("pardon", &req.actor, &req).await
+    }
+
+    pub async fn ban(&self, req: BanRequest) -> Result<(), Error> {
+        self.post_action("ban", &req.actor, &req).await
+    }
+
+    /// Returns [`Error::SS14ApiUnsupported`] if the server has no ban edit endpoint.
+    pub async fn edit_ban(&self, req: EditBanRequest) -> Result<(), Error> {
+        self.post_action("edit_ban", &req.actor, &req).await
+    }
+
+    pub async fn kick(&self, req: KickRequest) -> Result<(), Error> {
+        self.post_action("kick", &req.actor, &req).await
+    }
+
+    /// Returns [`Error::SS14ApiUnsupported`] if the server has no role ban endpoint.
+    pub async fn role_ban(&self, req: RoleBanRequest) -> Result<(), Error> {
+        self.post_action("role_ban", &req.actor, &req).await
+    }
+
+    /// Returns [`Error::SS14ApiUnsupported`] if the server has no role pardon endpoint.
+    pub async fn role_pardon(&self, req: PardonRequest) -> Result<(), Error> {
+        self.post_action("role_pardon", &req.actor, &req).await
+    }
+
+    pub async fn round_start(&self, req: RoundRequest) -> Result<(), Error> {
+        self.post_action("round/start", &req.actor, &req).await
+    }
+
+    pub async fn round_end(&self, req: RoundRequest) -> Result<(), Error> {
+        self.post_action("round/end", &req.actor, &req).await
+    }
+
+    pub async fn round_restart(&self, req: RoundRequest) -> Result<(), Error> {
+        self.post_action("round/restartnow", &req.actor, &req).await
+    }
+
+    /// Forces game preset of the next round.
+    pub async fn force_preset(&self, req: ForcePresetRequest) -> Result<(), Error> {
+        self.post_action("force_preset", &req.actor, &req).await
+    }
+
+    /// Every game rule known to the server.
+    pub async fn game_rules(&self) -> Result<Vec<String>, Error> {
+        let response = self.get::<GameRulesResponse>("game_rules").await?;
+        Ok(response.game_rules)
+    }
+
+    /// Current round info, including active game rules.
+    pub async fn info(&self) -> Result<InfoResponse, Error> {
+        self.get::<InfoResponse>("info").await
+    }
 
-        let request = self.inner.request(Method::POST, format!("{}/admin/actions/pardon", self.api_url))
+    pub async fn add_game_rule(&self, req: GameRuleRequest) -> Result<(), Error> {
+        self.post_action("add_game_rule", &req.actor, &req).await
+    }
+
+    pub async fn end_game_rule(&self, req: GameRuleRequest) -> Result<(), Error> {
+        self.post_action("end_game_rule", &req.actor, &req).await
+    }
+
+    /// Sends admin reply to player's ahelp (bwoink).
+    pub async fn admin_message(&self, req: AdminMessageRequest) -> Result<(), Error> {
+        self.post_action("admin_message", &req.actor, &req).await
+    }
+
+    /// Fetches public `/status` of the game server, `status_url` is its full address.
+    pub async fn status(&self, status_url: &str) -> Result<ServerStatus, Error> {
+        let response = self.inner.get(status_url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::ss14_api(&format!("Unable to fetch server status. Status: {}", status)));
+        }
+
+        let body = response.bytes().await?;
+        Ok(serde_json::from_slice::<ServerStatus>(&body)?)
+    }
+
+    async fn post_action<T: Serialize>(&self, action: &str, actor: &SS14ApiActor, req: &T) -> Result<(), Error> {
+        let actor = serde_json::to_string(actor)?;
+        let body = serde_json::to_string(req)?;
+
+        let request = self.inner.request(Method::POST, format!("{}/admin/actions/{}", self.api_url, action))
             .header("Authorization", format!("SS14Token {}", self.api_key))
             .header("Actor", actor)
             .body(body)
@@ -56,44 +138,37 @@ impl SS14ApiClient {
             return Ok(())
         }
 
-        if let Ok(bytes) = response.bytes().await {
-            let body = bytes.into_iter().collect::<Vec<_>>();
-            let err_response = serde_json::from_slice::<ErrorResponse>(&body);
-            match err_response {
-                Err(_) => Err(Error::ss14_api(&format!("Invalid error: {}", String::from_utf8_lossy(&body)))),
-                Ok(val) => Err(Error::from(val))
-            }
-        } else {
-            Err(Error::ss14_api(&format!("Unknown error occured at SS14 api. Status: {}", status)))
-        }
+        Err(Self::error_from_response(action, response, status).await)
     }
 
-    pub async fn ban(&self, req: BanRequest) -> Result<(), Error> {
-        let actor = serde_json::to_string(&req.actor)?;
-        let body = serde_json::to_string(&req)?;
-
-        let request = self.inner.request(Method::POST, format!("{}/admin/actions/ban", self.api_url))
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let request = self.inner.request(Method::GET, format!("{}/admin/{}", self.api_url, path))
             .header("Authorization", format!("SS14Token {}", self.api_key))
-            .header("Actor", actor)
-            .body(body)
             .build()?;
 
         let response = self.inner.execute(request).await?;
         let status = response.status().to_owned();
 
-        if status == 200 {
-            return Ok(())
+        if status != 200 {
+            return Err(Self::error_from_response(path, response, status).await);
         }
 
+        let body = response.bytes().await?;
+        Ok(serde_json::from_slice::<T>(&body)?)
+    }
+
+    async fn error_from_response(action: &str, response: reqwest::Response, status: reqwest::StatusCode) -> Error {
         if let Ok(bytes) = response.bytes().await {
             let body = bytes.into_iter().collect::<Vec<_>>();
             let err_response = serde_json::from_slice::<ErrorResponse>(&body);
             match err_response {
-                Err(_) => Err(Error::ss14_api(&format!("Invalid error: {}", String::from_utf8_lossy(&body)))),
-                Ok(val) => Err(Error::from(val))
+                // plain 404 means there is no such endpoint at all
+                Err(_) if status == 404 => Error::SS14ApiUnsupported(action.to_string()),
+                Err(_) => Error::ss14_api(&format!("Invalid error: {}", String::from_utf8_lossy(&body))),
+                Ok(val) => Error::from(val)
             }
         } else {
-            Err(Error::ss14_api(&format!("Unknown error occured at SS14 api. Status: {}", status)))
+            Error::ss14_api(&format!("Unknown error occured at SS14 api. Status: {}", status))
         }
     }
 }
@@ -120,6 +195,68 @@ impl std::fmt::Display for ErrorResponse {
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct GameRulesResponse {
+    #[serde(rename = "GameRules")]
+    game_rules: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct InfoResponse {
+    #[serde(rename = "RoundId")]
+    pub round_id: i32,
+    #[serde(rename = "GameRules", default)]
+    pub active_game_rules: Vec<String>,
+    #[serde(rename = "GamePreset", default)]
+    pub game_preset: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerStatus {
+    pub name: String,
+    pub players: i32,
+    pub soft_max_players: i32,
+    pub run_level: GameRunLevel,
+    #[serde(default)]
+    pub round_id: Option<i32>,
+    #[serde(default)]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub round_start_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "u8")]
+pub enum GameRunLevel {
+    PreRoundLobby,
+    InRound,
+    PostRound,
+}
+
+// SS14 sends run level as its enum number
+impl TryFrom<u8> for GameRunLevel {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::PreRoundLobby),
+            1 => Ok(Self::InRound),
+            2 => Ok(Self::PostRound),
+            _ => Err(format!("Unknown run level: {}", value)),
+        }
+    }
+}
+
+impl std::fmt::Display for GameRunLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PreRoundLobby => write!(f, "Lobby"),
+            Self::InRound => write!(f, "In Round"),
+            Self::PostRound => write!(f, "Post Round"),
+        }
+    }
+}
+
 // requests
 
 #[derive(Serialize, Debug)]
@@ -152,6 +289,10 @@ pub struct BanRequest {
     minutes: i32,
     #[serde(rename = "Severity")]
     severity: u16,
+    #[serde(rename = "Address", skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(rename = "BanHwid")]
+    ban_hwid: bool,
 
     #[serde(skip)]
     actor: SS14ApiActor
@@ -173,6 +314,178 @@ impl BanRequest {
             reason,
             minutes,
             severity,
+            address: None,
+            ban_hwid: false,
+            actor
+        }
+    }
+
+    /// Bans given address or CIDR range along with the player.
+    pub fn address(mut self, network: IpNetwork) -> Self {
+        self.address = Some(network.to_string());
+        self
+    }
+
+    /// Also bans player's last seen HWID.
+    pub fn ban_hwid(mut self, ban_hwid: bool) -> Self {
+        self.ban_hwid = ban_hwid;
+        self
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct EditBanRequest {
+    #[serde(rename = "BanId")]
+    ban_id: i32,
+    #[serde(rename = "Reason", skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    // counted from ban time, 0 is permanent
+    #[serde(rename = "Minutes", skip_serializing_if = "Option::is_none")]
+    minutes: Option<i32>,
+
+    #[serde(skip)]
+    actor: SS14ApiActor
+}
+
+impl EditBanRequest {
+    pub fn new(ban_id: i32, reason: Option<String>, minutes: Option<i32>, actor: SS14ApiActor) -> Self {
+        Self {
+            ban_id,
+            reason,
+            minutes,
+            actor
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct RoleBanRequest {
+    #[serde(rename = "Username")]
+    username: String,
+    #[serde(rename = "PlayerGuid")]
+    player_guid: String,
+    #[serde(rename = "Role")]
+    role: String,
+    #[serde(rename = "Reason")]
+    reason: String,
+    #[serde(rename = "Minutes")]
+    minutes: i32,
+    #[serde(rename = "Severity")]
+    severity: u16,
+
+    #[serde(skip)]
+    actor: SS14ApiActor
+}
+
+impl RoleBanRequest {
+    pub fn new(
+        username: String,
+        player_guid: Uuid,
+        role: String,
+        reason: String,
+        minutes: i32,
+        severity: u16,
+
+        actor: SS14ApiActor
+    ) -> Self {
+        Self {
+            username,
+            player_guid: player_guid.to_string(),
+            role,
+            reason,
+            minutes,
+            severity,
+            actor
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct KickRequest {
+    #[serde(rename = "Guid")]
+    guid: String,
+    #[serde(rename = "Reason")]
+    reason: String,
+
+    #[serde(skip)]
+    actor: SS14ApiActor
+}
+
+impl KickRequest {
+    pub fn new(player_guid: Uuid, reason: String, actor: SS14ApiActor) -> Self {
+        Self {
+            guid: player_guid.to_string(),
+            reason,
+            actor
+        }
+    }
+}
+
+// round actions carry nothing but the actor
+#[derive(Serialize, Debug)]
+pub struct RoundRequest {
+    #[serde(skip)]
+    actor: SS14ApiActor
+}
+
+impl RoundRequest {
+    pub fn new(actor: SS14ApiActor) -> Self {
+        Self { actor }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ForcePresetRequest {
+    #[serde(rename = "PresetId")]
+    preset_id: String,
+
+    #[serde(skip)]
+    actor: SS14ApiActor
+}
+
+impl ForcePresetRequest {
+    pub fn new(preset_id: String, actor: SS14ApiActor) -> Self {
+        Self {
+            preset_id,
+            actor
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct GameRuleRequest {
+    #[serde(rename = "GameRuleId")]
+    game_rule_id: String,
+
+    #[serde(skip)]
+    actor: SS14ApiActor
+}
+
+impl GameRuleRequest {
+    pub fn new(game_rule_id: String, actor: SS14ApiActor) -> Self {
+        Self {
+            game_rule_id,
+            actor
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct AdminMessageRequest {
+    #[serde(rename = "Guid")]
+    guid: String,
+    #[serde(rename = "Message")]
+    message: String,
+
+    #[serde(skip)]
+    actor: SS14ApiActor
+}
+
+impl AdminMessageRequest {
+    pub fn new(player_guid: Uuid, message: String, actor: SS14ApiActor) -> Self {
+        Self {
+            guid: player_guid.to_string(),
+            message,
             actor
         }
     }
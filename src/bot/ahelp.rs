@@ -0,0 +1,112 @@
+use log::{error, warn};
+use serenity::all::{Channel, ChannelId, ChannelType, Context, CreateAllowedMentions, CreateMessage, CreateThread, Http, Message, ReactionType};
+use uuid::Uuid;
+
+use crate::{api::ss14client::{AdminMessageRequest, SS14ApiActor}, bot::{permissions::check_key_permissions, utilities::{prepare_clients, resolve_admin, shorten_message}}, config::Config, database::PgDatabase, error::Error};
+
+// discord allows 2000 symbols per message, leave room for sender and ellipsis
+static MAX_MESSAGE_LEN: usize = 1900;
+// discord allows 100 symbols per thread name
+static MAX_THREAD_NAME_LEN: usize = 90;
+
+/// Ahelp message posted by SS14 server, `sender` is the player or in-game admin who wrote it.
+pub struct AhelpMessage {
+    pub player: Uuid,
+    pub username: String,
+    pub sender: String,
+    pub message: String,
+}
+
+/// Posts ahelp message to player's thread, creating a new one on first ahelp or if old one is gone.
+pub async fn relay_to_discord(ahelp: AhelpMessage, channel: ChannelId, http: &Http, db: &PgDatabase) -> Result<ChannelId, Error> {
+    let message = CreateMessage::new()
+        .content(format!("**{}:** {}", ahelp.sender, shorten_message(&ahelp.message, MAX_MESSAGE_LEN)))
+        .allowed_mentions(CreateAllowedMentions::new());
+
+    if let Some(thread_id) = db.get_ahelp_thread(&ahelp.player).await? {
+        let thread = ChannelId::new(thread_id as u64);
+        match thread.send_message(http, message.clone()).await {
+            Ok(_) => return Ok(thread),
+            Err(e) => warn!("Ahelp thread {} of {} is unavailable, creating new one: {}", thread, ahelp.username, e),
+        }
+    }
+
+    let name = shorten_message(&format!("Ahelp: {}", ahelp.username), MAX_THREAD_NAME_LEN);
+    let thread = channel.create_thread(http, CreateThread::new(name).kind(ChannelType::PublicThread)).await?;
+    db.set_ahelp_thread(&ahelp.player, thread.id.get() as i64).await?;
+
+    thread.id.send_message(http, message).await?;
+    Ok(thread.id)
+}
+
+/// Checks message was written in a thread of ahelp channel, so other messages never reach the database.
+/// Answered from guild cache (needs `GUILDS` intent), only threads missing from it, e.g. archived ones, cost an HTTP request.
+pub async fn is_ahelp_thread(msg: &Message, ctx: &Context, ahelp_channel: ChannelId) -> bool {
+    let cached = msg.guild_id.and_then(|guild_id| ctx.cache.guild(guild_id)).and_then(|guild| {
+        if guild.channels.contains_key(&msg.channel_id) {
+            return Some(false); // regular channel, threads are kept separately
+        }
+        guild.threads.iter()
+            .find(|thread| thread.id == msg.channel_id)
+            .map(|thread| thread.parent_id == Some(ahelp_channel))
+    });
+    if let Some(is_ahelp_thread) = cached {
+        return is_ahelp_thread;
+    }
+
+    match msg.channel_id.to_channel(&ctx.http).await {
+        Ok(Channel::Guild(channel)) => channel.thread_metadata.is_some() && channel.parent_id == Some(ahelp_channel),
+        Ok(_) => false,
+        Err(e) => {
+            warn!("Unable to fetch channel {} of message {}: {}", msg.channel_id, msg.id, e);
+            false
+        }
+    }
+}
+
+/// Sends admin's message written in ahelp thread back to the player in-game.
+/// Messages outside of ahelp threads are ignored.
+pub async fn relay_to_game(msg: &Message, http: &Http, db: &PgDatabase, config: &Config) {
+    let player = match db.get_ahelp_player(msg.channel_id.get() as i64).await {
+        Ok(Some(player)) => player,
+        Ok(None) => return,
+        Err(e) => {
+            error!("Error looking up ahelp thread {}: {}", msg.channel_id, e);
+            return;
+        }
+    };
+
+    let result = send_admin_message(player, msg, db, config).await;
+    match result {
+        Ok(_) => {
+            if let Err(e) = msg.react(http, ReactionType::Unicode("✅".to_string())).await {
+                error!("Error reacting to ahelp reply {}: {}", msg.id, e);
+            }
+        },
+        Err(e) => {
+            if let Err(e) = msg.reply(http, format!("Message wasn't delivered: {}", e)).await {
+                error!("Error replying to ahelp reply {}: {}", msg.id, e);
+            }
+        }
+    }
+}
+
+async fn send_admin_message(player: Uuid, msg: &Message, db: &PgDatabase, config: &Config) -> Result<(), String> {
+    check_key_permissions("ahelp.reply", msg.author.id, db, config).await?;
+
+    let (ss14_api, discord_api) = prepare_clients(config)?;
+    let (admin_uuid, admin_name) = resolve_admin(msg.author.id, &discord_api, db).await?;
+
+    let request = AdminMessageRequest::new(player, msg.content.clone(), SS14ApiActor::new(admin_uuid, &admin_name));
+    match ss14_api.admin_message(request).await {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Unable to send ahelp reply to {}. Error: {e}", player);
+            if let Error::SS14ApiError(e) = e {
+                Err(e)
+            } else {
+                Err("Error occured sending message to the game server.".to_string())
+            }
+        }
+    }
+}
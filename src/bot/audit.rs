@@ -0,0 +1,115 @@
+use log::{error, info};
+use serenity::all::{CreateAllowedMentions, CreateEmbed, CreateEmbedFooter, CreateMessage, Http, Timestamp, UserId};
+use uuid::Uuid;
+
+use crate::{bot::utilities::{generate_random_colour, prepare_clients, resolve_admin, shorten_message}, config::Config, database::{NewAuditRecord, PgDatabase}};
+
+// discord allows 1024 symbols in embed field value, leave room for ellipsis
+static MAX_FIELD_LEN: usize = 1000;
+
+/// Successful mutating action taken through the bot or its HTTP API.
+/// `action` uses permission key naming, e.g. `bans.ban`.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    actor: Option<UserId>, // None for HTTP API calls
+    admin: Option<(Uuid, String)>,
+    action: &'static str,
+    target: String,
+    parameters: Vec<(&'static str, String)>,
+}
+
+impl AuditEntry {
+    pub fn new(actor: UserId, action: &'static str, target: impl Into<String>) -> Self {
+        Self { actor: Some(actor), admin: None, action, target: target.into(), parameters: vec![] }
+    }
+
+    /// Action made through HTTP API, there is no Discord user behind it, so `admin` should be set.
+    pub fn api(action: &'static str, target: impl Into<String>) -> Self {
+        Self { actor: None, admin: None, action, target: target.into(), parameters: vec![] }
+    }
+
+    /// SS14 admin behind the actor, when command resolved it already.
+    pub fn admin(mut self, uuid: Uuid, name: &str) -> Self {
+        self.admin = Some((uuid, name.to_string()));
+        self
+    }
+
+    pub fn param(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.parameters.push((name, value.to_string()));
+        self
+    }
+}
+
+/// Stores entry in `void_relay_audit` and posts it to audit channel if one is configured.
+/// Failures are only logged, action itself is already done at this point.
+pub async fn record(entry: AuditEntry, http: &Http, db: &PgDatabase, config: &Config) {
+    let actor = format_actor(entry.actor);
+    info!("Audit: {} by {} on {} {:?}", entry.action, actor, entry.target, entry.parameters);
+
+    let (admin_uuid, admin_name) = match (entry.admin.clone(), entry.actor) {
+        (Some((uuid, name)), _) => (Some(uuid), name),
+        (None, Some(discord_id)) => resolve_actor(discord_id, db, config).await,
+        (None, None) => (None, "Unknown".to_string()),
+    };
+
+    let parameters = entry.parameters.iter()
+        .map(|(name, value)| (name.to_string(), serde_json::Value::String(value.clone())))
+        .collect::<serde_json::Map<_, _>>();
+
+    let record = NewAuditRecord {
+        actor_discord_id: entry.actor.map(|actor| actor.get() as i64),
+        actor_user_id: admin_uuid,
+        actor_name: admin_name.clone(),
+        action: entry.action.to_string(),
+        target: entry.target.clone(),
+        parameters: serde_json::Value::Object(parameters).to_string(),
+    };
+
+    if let Err(e) = db.add_audit_record(record).await {
+        error!("Error storing audit record of {}: {}", entry.action, e);
+    }
+
+    let channel = match config.audit_channel() {
+        Some(channel) => channel,
+        None => return,
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("🛡️ {}", entry.action))
+        .field("Actor", actor, true)
+        .field("SS14 Admin", admin_name, true)
+        .field("Target", shorten_message(&entry.target, MAX_FIELD_LEN), true)
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"))
+        .timestamp(Timestamp::now());
+
+    for (name, value) in &entry.parameters {
+        embed = embed.field(*name, shorten_message(value, MAX_FIELD_LEN), false);
+    }
+
+    // audit shouldn't ping anyone it mentions
+    let message = CreateMessage::new().embed(embed).allowed_mentions(CreateAllowedMentions::new());
+    if let Err(e) = channel.send_message(http, message).await {
+        error!("Error posting audit record of {} to {}: {}", entry.action, channel, e);
+    }
+}
+
+/// Discord mention of the actor, `HTTP API` when there is none.
+pub fn format_actor(actor: Option<UserId>) -> String {
+    match actor {
+        Some(actor) => format!("<@{}>", actor),
+        None => "HTTP API".to_string(),
+    }
+}
+
+async fn resolve_actor(actor: UserId, db: &PgDatabase, config: &Config) -> (Option<Uuid>, String) {
+    let discord_api = match prepare_clients(config) {
+        Ok((_, discord_api)) => discord_api,
+        Err(_) => return (None, "Unknown".to_string()),
+    };
+
+    match resolve_admin(actor, &discord_api, db).await {
+        Ok((uuid, name)) => (Some(uuid), name),
+        Err(_) => (None, "Unknown".to_string()),
+    }
+}
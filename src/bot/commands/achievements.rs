@@ -0,0 +1,95 @@
+use std::{sync::Arc, time::Duration};
+
+use log::error;
+use serenity::all::{Builder, CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, CreateInteractionResponseFollowup, EditInteractionResponse, Http, ResolvedOption, UserId};
+use tokio::sync::watch;
+
+use crate::{bot::{audit::{self, AuditEntry}, create_response_with_content, tasks::achievements::{self, SyncProgress}}, config::Config, database::PgDatabase};
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("achievements")
+        .description("Playtime based Discord roles")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "sync",
+            "Forces playtime roles sync right now",
+        ))
+}
+
+pub fn get_options(options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<AchievementsSubcommand, String> {
+    if options.len() != 1 {
+        return Err("Invalid options count".to_string());
+    }
+
+    match options.first().unwrap().name {
+        "sync" => Ok(AchievementsSubcommand::Sync { caller_discord_id: cmd.user.id, token: cmd.token.clone() }),
+        _ => Err("Invalid subcommand.".to_string())
+    }
+}
+
+// keeps progress edits of the response well below discord rate limits
+static PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sync runs in background, original response shows its progress and result.
+pub async fn execute(cmd: AchievementsSubcommand, db: &PgDatabase, config: &Config, http: Arc<Http>) -> CreateInteractionResponseFollowup {
+    match cmd {
+        AchievementsSubcommand::Sync { caller_discord_id, token } => {
+            if config.achievements().is_empty() {
+                return create_response_with_content("No achievement rules configured.", true);
+            }
+
+            tokio::spawn(run_sync(caller_discord_id, token, http, db.clone(), config.clone()));
+            create_response_with_content("Achievements sync started, progress is shown above.", true)
+        }
+    }
+}
+
+async fn run_sync(caller: UserId, token: String, http: Arc<Http>, db: PgDatabase, config: Config) {
+    let (progress, mut updates) = watch::channel(SyncProgress::default());
+
+    let reporter_http = http.clone();
+    let reporter_token = token.clone();
+    let reporter = tokio::spawn(async move {
+        while updates.changed().await.is_ok() {
+            let SyncProgress { checked, total } = *updates.borrow_and_update();
+            show(&reporter_http, &reporter_token, &format!("Syncing achievements: {}/{} linked members checked...", checked, total)).await;
+            tokio::time::sleep(PROGRESS_INTERVAL).await;
+        }
+    });
+
+    let result = achievements::sync(&http, &db, &config, Some(&progress)).await;
+    reporter.abort();
+
+    let message = match result {
+        Ok(report) => {
+            let entry = AuditEntry::new(caller, "achievements.sync", "all players")
+                .param("granted", report.granted)
+                .param("revoked", report.revoked);
+            audit::record(entry, &http, &db, &config).await;
+
+            format!(
+                "Synced achievements of {} players: {} roles granted, {} roles revoked.",
+                report.players, report.granted, report.revoked
+            )
+        },
+        Err(e) => {
+            error!("Error syncing achievements: {e}");
+            "Failed to sync achievements.".to_string()
+        }
+    };
+    show(&http, &token, &message).await;
+}
+
+// interaction token lives for 15 minutes, longer syncs only end up in logs and audit
+async fn show(http: &Http, token: &str, content: &str) {
+    if let Err(e) = EditInteractionResponse::new().content(content).execute(http, token).await {
+        error!("Error updating achievements sync response: {e}");
+    }
+}
+
+pub enum AchievementsSubcommand {
+    Sync {
+        caller_discord_id: UserId,
+        token: String, // to edit deferred response while sync runs
+    },
+}
@@ -0,0 +1,87 @@
+use log::error;
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, ResolvedOption, ResolvedValue};
+
+use crate::{bot::{create_response_with_content, utilities::{generate_random_colour, get_user_id_by_login}}, database::{AltAccount, PgDatabase}};
+
+// keeps embed description below discord limit
+static MAX_ALTS: i64 = 25;
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("alts")
+        .description("Finds accounts which shared IP or HWID with the player")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "login",
+                "In-Game login",
+            )
+            .set_autocomplete(true)
+            .required(true),
+        )
+}
+
+pub fn get_options(options: &[ResolvedOption]) -> Result<String, String> {
+    match options.first() {
+        Some(ResolvedOption { name: "login", value: ResolvedValue::String(login), .. }) => Ok(login.to_string()),
+        _ => Err("Invalid or missing 'login' option".to_string()),
+    }
+}
+
+pub async fn execute(login: String, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+    let uuid = match get_user_id_by_login(&login, db).await {
+        Some(uuid) => uuid,
+        None => return create_response_with_content("No such player found.", true),
+    };
+
+    let alts = match db.get_alt_accounts(&uuid, MAX_ALTS).await {
+        Ok(alts) => alts,
+        Err(e) => {
+            error!("Error retrieving alts of {}. Error {}", login, e);
+            return create_response_with_content("Failed to retrieve alt accounts.", true);
+        }
+    };
+
+    let mut description = alts.iter()
+        .map(format_alt)
+        .collect::<Vec<String>>()
+        .join("\n");
+    if description.is_empty() {
+        description = "Nothing found.".to_string();
+    }
+
+    let banned = alts.iter().filter(|alt| alt.banned).count();
+    let embed = CreateEmbed::new()
+        .title(format!("Possible alts of `{}`", login))
+        .description(description)
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("{} found • {} banned • VoidRelay By JerryImMouse", alts.len(), banned)));
+
+    CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
+}
+
+fn format_alt(alt: &AltAccount) -> String {
+    let mut formatted = format!(
+        "{} `{}`",
+        if alt.banned { "🔒" } else { "👤" },
+        alt.user_name,
+    );
+
+    if alt.address_overlaps > 0 {
+        formatted.push_str(&format!(" 📍 {} IP", alt.address_overlaps));
+    }
+    if alt.hwid_overlaps > 0 {
+        formatted.push_str(&format!(" 🖥️ {} HWID", alt.hwid_overlaps));
+    }
+
+    formatted.push_str(&format!(
+        " • <t:{}:d> — <t:{}:d>",
+        alt.first_overlap.timestamp(),
+        alt.last_overlap.timestamp(),
+    ));
+
+    if alt.banned {
+        formatted.push_str(" **(banned)**");
+    }
+
+    formatted
+}
@@ -0,0 +1,165 @@
+use log::error;
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, ResolvedOption, ResolvedValue, UserId};
+
+use crate::{bot::{audit::format_actor, create_response_with_content, utilities::{generate_random_colour, shorten_message}}, database::{AuditFilter, AuditRecord, PgDatabase}};
+
+static DEFAULT_LIMIT: i64 = 10;
+static MAX_LIMIT: i64 = 25;
+static SHORT_MSG_LEN_SYMBOLS: usize = 80;
+static SHORT_TARGET_LEN_SYMBOLS: usize = 40;
+// embed description limit is 4096
+static MAX_DESCRIPTION_LEN: usize = 4000;
+
+// actions recorded by bot::audit, same as their permission keys
+static ACTIONS: &[&str] = &[
+    "whitelist.add",
+    "whitelist.rm",
+    "notes.add",
+    "notes.edit",
+    "notes.delete",
+    "bans.ban",
+    "bans.pardon",
+    "bans.edit",
+    "rolebans.ban",
+    "rolebans.pardon",
+    "kick",
+    "achievements.sync",
+    "round.start",
+    "round.end",
+    "round.restart",
+    "round.preset",
+    "gamerules.add",
+    "gamerules.end",
+];
+
+pub fn get_registration() -> CreateCommand {
+    let mut action = CreateCommandOption::new(
+        CommandOptionType::String,
+        "action",
+        "Performed action",
+    )
+    .required(false);
+    for a in ACTIONS {
+        action = action.add_string_choice(*a, *a);
+    }
+
+    CreateCommand::new("audit")
+        .description("Moderation actions taken through the bot")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "search",
+                "Searches audit log, newest first",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "actor",
+                    "Discord user who performed the action",
+                )
+                .required(false),
+            )
+            .add_sub_option(action)
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "target",
+                    "Part of action target, e.g. login or ban id",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "limit",
+                    "How many records to show",
+                )
+                .min_int_value(1)
+                .max_int_value(MAX_LIMIT as u64)
+                .required(false),
+            ),
+        )
+}
+
+pub fn get_options(options: &[ResolvedOption]) -> Result<AuditSubcommand, String> {
+    if options.len() != 1 {
+        return Err("Invalid options count".to_string());
+    }
+
+    let subcommand = options.first().unwrap();
+    let suboptions = match (subcommand.name, &subcommand.value) {
+        ("search", ResolvedValue::SubCommand(suboptions)) => suboptions,
+        _ => return Err("Invalid subcommand.".to_string())
+    };
+
+    let mut filter = AuditFilter::default();
+    let mut limit = DEFAULT_LIMIT;
+
+    for option in suboptions {
+        match (option.name, &option.value) {
+            ("actor", ResolvedValue::User(user, _)) => filter.actor_discord_id = Some(user.id.get() as i64),
+            ("action", ResolvedValue::String(a)) => filter.action = Some(a.to_string()),
+            ("target", ResolvedValue::String(t)) => filter.target = Some(t.to_string()),
+            ("limit", ResolvedValue::Integer(l)) => limit = (*l).clamp(1, MAX_LIMIT),
+            _ => return Err("Invalid options passed".to_string())
+        }
+    }
+
+    Ok(AuditSubcommand::Search { filter, limit })
+}
+
+pub async fn execute(cmd: AuditSubcommand, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+    match cmd {
+        AuditSubcommand::Search { filter, limit } => {
+            let records = match db.search_audit(&filter, limit).await {
+                Ok(records) => records,
+                Err(e) => {
+                    error!("Error searching audit log: {e}");
+                    return create_response_with_content("Failed to search audit log.", true);
+                }
+            };
+
+            let mut description = String::new();
+            for (shown, record) in records.iter().enumerate() {
+                let row = format_audit_record(record);
+                if description.chars().count() + row.chars().count() + 1 > MAX_DESCRIPTION_LEN {
+                    description.push_str(&format!("… and {} more, narrow the search", records.len() - shown));
+                    break;
+                }
+                description.push_str(&row);
+                description.push('\n');
+            }
+            if description.is_empty() {
+                description = "Nothing found.".to_string();
+            }
+
+            let embed = CreateEmbed::new()
+                .title("Audit log")
+                .description(description)
+                .color(generate_random_colour())
+                .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+            CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
+        }
+    }
+}
+
+fn format_audit_record(record: &AuditRecord) -> String {
+    format!(
+        "**{}**. <t:{}:f> {} (`{}`) `{}` → `{}` {}",
+        record.void_relay_audit_id,
+        record.time.timestamp(),
+        format_actor(record.actor_discord_id.map(|id| UserId::new(id as u64))),
+        shorten_message(&record.actor_name, SHORT_TARGET_LEN_SYMBOLS),
+        record.action,
+        shorten_message(&record.target, SHORT_TARGET_LEN_SYMBOLS),
+        shorten_message(&record.parameters, SHORT_MSG_LEN_SYMBOLS),
+    )
+}
+
+pub enum AuditSubcommand {
+    Search {
+        filter: AuditFilter,
+        limit: i64,
+    },
+}
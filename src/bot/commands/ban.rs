@@ -1,9 +1,16 @@
-use log::{error, warn};
-use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, ResolvedOption, ResolvedValue, UserId};
+use std::time::Duration as StdDuration;
 
-use crate::{api::{discord_client::DiscordApiClient, ss14client::{BanRequest, PardonRequest, SS14ApiActor, SS14ApiClient}}, bot::{create_response_with_content, utilities::{generate_random_colour, get_user_id_by_login, resolve_user_name}}, config::Config, database::{PgDatabase, ServerBan, ServerBanShort}, error::Error};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use log::{error, info};
+use serenity::all::{ActionRowComponent, CommandInteraction, CommandOptionType, CreateActionRow, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInputText, CreateInteractionResponseFollowup, CreateModal, Http, InputTextStyle, ModalInteraction, ResolvedOption, ResolvedValue, UserId};
+use sqlx::types::ipnetwork::IpNetwork;
+use uuid::Uuid;
+
+use crate::{api::ss14client::{BanRequest, EditBanRequest, PardonRequest, SS14ApiActor, SS14ApiClient}, bot::{audit::{self, AuditEntry}, confirmation::{Confirmations, PendingAction}, create_response_with_content, pagination::{page_buttons, pages_count, SavedQueries, PER_PAGE}, permissions::check_duration_limit, utilities::{day_start, format_date, format_duration, format_hwid, format_network, generate_random_colour, get_user_id_by_login, parse_date, parse_duration, parse_network, prepare_clients, resolve_admin, resolve_optional_user_name, resolve_user_name, shorten_message}}, config::{BanTemplate, Config, Redaction}, database::{BanFilter, BanSearchResult, BanState, PgDatabase, PlayerProfile, ServerBan, ServerBanEdit, ServerBanShort}, error::Error};
 
 static SHORT_MSG_LEN_SYMBOLS: usize = 50;
+// embed field value limit is 1024
+static MAX_FIELD_LEN: usize = 1000;
 
 pub fn get_registration() -> CreateCommand {
     CreateCommand::new("bans")
@@ -20,6 +27,7 @@ pub fn get_registration() -> CreateCommand {
                     "login",
                     "In-Game login",
                 )
+                .set_autocomplete(true)
                 .required(true),
             ),
         )
@@ -53,6 +61,37 @@ pub fn get_registration() -> CreateCommand {
                 .required(true),
             ),
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "edit",
+                "Changes duration or reason of specific ban",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "id",
+                    "Ban ID",
+                )
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "duration",
+                    "New duration counted from ban time, e.g. `3d`, `2w` or `perm`",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "reason",
+                    "New ban reason",
+                )
+                .required(false),
+            ),
+        )
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::SubCommand,
@@ -65,13 +104,14 @@ pub fn get_registration() -> CreateCommand {
                     "login",
                     "In-Game Login",
                 )
+                .set_autocomplete(true)
                 .required(true),
             )
             .add_sub_option(
                 CreateCommandOption::new(
-                    CommandOptionType::Integer,
-                    "minutes",
-                    "Minutes to ban player for",
+                    CommandOptionType::String,
+                    "duration",
+                    "Ban duration, e.g. `30m`, `3d`, `2w`, `1mo` or `perm`",
                 )
                 .required(false),
             )
@@ -79,8 +119,17 @@ pub fn get_registration() -> CreateCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "reason",
-                    "Reason to ban for",
+                    "Reason to ban for, omit to type it in a form",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "template",
+                    "Ban reason template",
                 )
+                .set_autocomplete(true)
                 .required(false),
             )
             .add_sub_option(
@@ -90,6 +139,89 @@ pub fn get_registration() -> CreateCommand {
                     "Severity to ban for",
                 )
                 .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "ip",
+                    "Also ban IP or CIDR range, e.g. `10.0.0.0/24`",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "hwid",
+                    "Also ban player's last seen HWID",
+                )
+                .required(false),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "search",
+                "Searches all bans, newest first",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "ip",
+                    "IP or CIDR range, e.g. `10.0.0.0/24`",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "admin",
+                    "Banning admin's In-Game login",
+                )
+                .set_autocomplete(true)
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "from",
+                    "First day to show, e.g. `2025-01-31`",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "to",
+                    "Last day to show, e.g. `2025-02-28`",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "state",
+                    "Ban state",
+                )
+                .add_string_choice("Active", "active")
+                .add_string_choice("Expired", "expired")
+                .add_string_choice("Pardoned", "pardoned")
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "reason",
+                    "Part of ban reason",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "round",
+                    "Round ID",
+                )
+                .required(false),
             ),
         )
 }
@@ -131,7 +263,101 @@ fn parse_pardon_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Resul
     Err("Invalid or missing 'id' option".to_string())
 }
 
-fn parse_ban_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<BansSubcommand, String> {
+fn parse_edit_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<BansSubcommand, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        let mut id: Option<i64> = None;
+        let mut minutes: Option<i64> = None;
+        let mut reason: Option<String> = None;
+
+        for option in suboptions {
+            match (option.name, &option.value) {
+                ("id", ResolvedValue::Integer(i)) => id = Some(*i),
+                ("duration", ResolvedValue::String(d)) => minutes = Some(parse_duration(d)?),
+                ("reason", ResolvedValue::String(r)) => reason = Some(r.to_string()),
+                _ => return Err("Invalid options passed".to_string())
+            }
+        }
+
+        let id = match id {
+            Some(id) => id as i32,
+            None => return Err("Invalid or missing 'id' option".to_string()),
+        };
+
+        if minutes.is_none() && reason.is_none() {
+            return Err("Nothing to edit, specify `duration` or `reason`.".to_string());
+        }
+
+        return Ok(BansSubcommand::Edit { caller_discord_id: cmd.user.id, id, minutes, reason });
+    }
+    Err("Invalid options provided.".to_string())
+}
+
+fn parse_search_options(opt: &ResolvedOption) -> Result<BansSubcommand, String> {
+    let suboptions = match &opt.value {
+        ResolvedValue::SubCommand(suboptions) => suboptions,
+        _ => return Err("Invalid options passed".to_string()),
+    };
+
+    let mut filter = BanFilter::default();
+    let mut admin_login: Option<String> = None;
+    let mut from: Option<NaiveDate> = None;
+    let mut to: Option<NaiveDate> = None;
+
+    for option in suboptions {
+        match (option.name, &option.value) {
+            ("ip", ResolvedValue::String(ip)) => filter.address = Some(parse_network(ip)?),
+            ("admin", ResolvedValue::String(a)) => admin_login = Some(a.to_string()),
+            ("from", ResolvedValue::String(d)) => from = Some(parse_date(d)?),
+            ("to", ResolvedValue::String(d)) => to = Some(parse_date(d)?),
+            ("state", ResolvedValue::String(s)) => filter.state = Some(match *s {
+                "active" => BanState::Active,
+                "expired" => BanState::Expired,
+                "pardoned" => BanState::Pardoned,
+                _ => return Err(format!("Unknown ban state: `{}`", s)),
+            }),
+            ("reason", ResolvedValue::String(r)) => filter.reason = Some(r.to_string()),
+            ("round", ResolvedValue::Integer(r)) => filter.round_id = Some(*r as i32),
+            _ => return Err("Invalid options passed".to_string())
+        }
+    }
+
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err("`from` date is after `to` date.".to_string());
+        }
+    }
+    filter.from = from.map(day_start);
+    // whole last day is included
+    filter.to = to.map(|d| day_start(d) + Duration::days(1));
+
+    let mut summary = Vec::new();
+    if let Some(address) = &filter.address {
+        summary.push(format!("📍 `{}`", format_network(address)));
+    }
+    if let Some(admin) = &admin_login {
+        summary.push(format!("✍️ `{}`", admin));
+    }
+    if from.is_some() || to.is_some() {
+        let date = |d: Option<NaiveDate>| d.as_ref().map(format_date).unwrap_or("…".to_string());
+        summary.push(format!("📅 {} — {}", date(from), date(to)));
+    }
+    if let Some(state) = filter.state {
+        summary.push(format!("🔖 {:?}", state));
+    }
+    if let Some(reason) = &filter.reason {
+        summary.push(format!("📝 `{}`", reason));
+    }
+    if let Some(round_id) = filter.round_id {
+        summary.push(format!("✨ Round {}", round_id));
+    }
+
+    Ok(BansSubcommand::Search {
+        admin_login,
+        search: BanSearch { filter, summary: summary.join(" • ") },
+    })
+}
+
+fn parse_ban_options(opt: &ResolvedOption, cmd: &CommandInteraction, config: &Config) -> Result<BansSubcommand, String> {
     let caller_discord_id = cmd.user.id;
 
     if let ResolvedValue::SubCommand(suboptions) = &opt.value {
@@ -139,23 +365,43 @@ fn parse_ban_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<B
         let mut minutes: Option<i64> = None;
         let mut reason: Option<String> = None;
         let mut severity: Option<u16> = None;
+        let mut template: Option<&BanTemplate> = None;
+        let mut address: Option<IpNetwork> = None;
+        let mut ban_hwid = false;
         
         for option in suboptions {
             match (option.name, &option.value) {
                 ("login", ResolvedValue::String(login)) => banning_player_login = Some(login.to_string()),
-                ("minutes", ResolvedValue::Integer(min)) => minutes = Some(*min),
+                ("duration", ResolvedValue::String(d)) => minutes = Some(parse_duration(d)?),
                 ("reason", ResolvedValue::String(r)) => reason = Some(r.to_string()),
                 ("severity", ResolvedValue::Integer(s)) => severity = Some(*s as u16),
+                ("template", ResolvedValue::String(t)) => match config.ban_template(t) {
+                    Some(t) => template = Some(t),
+                    None => return Err(format!("Unknown ban template: `{}`", t)),
+                },
+                ("ip", ResolvedValue::String(ip)) => address = Some(parse_network(ip)?),
+                ("hwid", ResolvedValue::Boolean(h)) => ban_hwid = *h,
                 _ => return Err("Invalid options passed".to_string())
             }
         }
 
+        // explicitly passed options win over template defaults
+        if let Some(template) = template {
+            if minutes.is_none() {
+                minutes = Some(parse_duration(&template.duration)?);
+            }
+            severity = severity.or(Some(template.severity));
+        }
+
         if banning_player_login.is_none() {
             return Err("Banning player couldn't be none".to_string())
         }
 
         let banning_player_login = banning_player_login.unwrap();
-        let minutes = minutes.unwrap_or(0);
+        let minutes = match minutes {
+            Some(minutes) => minutes,
+            None => return Err("Ban duration is required, use `perm` for permanent bans.".to_string()),
+        };
         let reason = reason.unwrap_or("No reason provided".to_string());
         let severity = severity.unwrap_or(2);
 
@@ -164,14 +410,128 @@ fn parse_ban_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<B
             banning_player_login, 
             minutes, 
             reason, 
-            severity 
+            severity,
+            address,
+            ban_hwid,
         })
     }
 
-    todo!()
+    Err("Invalid options provided.".to_string())
+}
+
+pub static BAN_MODAL_PREFIX: &str = "ban_modal";
+
+/// Builds a form with multi-line reason for `/bans ban` invoked without `reason`.
+/// Fields are prefilled from chosen template and passed options, so they stay editable.
+pub fn get_ban_modal(options: &[ResolvedOption], config: &Config) -> Option<CreateModal> {
+    let suboptions = match options.first() {
+        Some(ResolvedOption { name: "ban", value: ResolvedValue::SubCommand(suboptions), .. }) => suboptions,
+        _ => return None,
+    };
+
+    let mut login: Option<&str> = None;
+    let mut duration: Option<String> = None;
+    let mut severity: Option<i64> = None;
+    let mut template: Option<&BanTemplate> = None;
+    let mut address: Option<IpNetwork> = None;
+    let mut ban_hwid = false;
+
+    for option in suboptions {
+        match (option.name, &option.value) {
+            ("login", ResolvedValue::String(l)) => login = Some(l),
+            ("duration", ResolvedValue::String(d)) => duration = Some(d.to_string()),
+            ("severity", ResolvedValue::Integer(s)) => severity = Some(*s),
+            // unknown template is reported by get_options
+            ("template", ResolvedValue::String(t)) => template = Some(config.ban_template(t)?),
+            // same for invalid address
+            ("ip", ResolvedValue::String(ip)) => address = Some(parse_network(ip).ok()?),
+            ("hwid", ResolvedValue::Boolean(h)) => ban_hwid = *h,
+            ("reason", _) => return None,
+            _ => {}
+        }
+    }
+
+    let reason = template.map(|t| t.reason.clone()).unwrap_or_default();
+    let duration = duration.or(template.map(|t| t.duration.clone())).unwrap_or_default();
+    let severity = severity.or(template.map(|t| t.severity as i64)).unwrap_or(2);
+
+    let mut reason_input = CreateInputText::new(InputTextStyle::Paragraph, "Reason", "reason").required(true);
+    if !reason.is_empty() {
+        reason_input = reason_input.value(reason);
+    }
+    let mut duration_input = CreateInputText::new(InputTextStyle::Short, "Duration", "duration")
+        .placeholder("30m, 3d, 2w, 1mo or perm")
+        .required(true);
+    if !duration.is_empty() {
+        duration_input = duration_input.value(duration);
+    }
+    let severity_input = CreateInputText::new(InputTextStyle::Short, "Severity", "severity")
+        .value(severity.to_string())
+        .required(true);
+
+    // options without form fields travel in custom id as `login;address;hwid`
+    let address = address.map(|a| a.to_string()).unwrap_or_default();
+    let custom_id = format!("{}:{};{};{}", BAN_MODAL_PREFIX, login?, address, ban_hwid as u8);
+
+    Some(CreateModal::new(custom_id, "Ban player").components(vec![
+        CreateActionRow::InputText(reason_input),
+        CreateActionRow::InputText(duration_input),
+        CreateActionRow::InputText(severity_input),
+    ]))
+}
+
+/// Parses form submitted from [`get_ban_modal`].
+pub fn parse_ban_modal(modal: &ModalInteraction) -> Result<BansSubcommand, String> {
+    let mut key = modal.data.custom_id.split_once(':').map(|(_, key)| key).unwrap_or_default().split(';');
+    let banning_player_login = match key.next() {
+        Some(login) if !login.is_empty() => login.to_string(),
+        _ => return Err("Banning player couldn't be none".to_string()),
+    };
+    let address = match key.next() {
+        Some(address) if !address.is_empty() => Some(parse_network(address)?),
+        _ => None,
+    };
+    let ban_hwid = key.next() == Some("1");
+
+    let mut reason: Option<String> = None;
+    let mut minutes: Option<i64> = None;
+    let mut severity: Option<u16> = None;
+
+    for row in &modal.data.components {
+        for component in &row.components {
+            if let ActionRowComponent::InputText(input) = component {
+                let value = input.value.as_deref().unwrap_or_default().trim();
+                match input.custom_id.as_str() {
+                    "reason" => reason = Some(value.to_string()),
+                    "duration" => minutes = Some(parse_duration(value)?),
+                    "severity" => severity = Some(value.parse().map_err(|_| "Severity must be a non-negative number.".to_string())?),
+                    _ => return Err("Invalid options passed".to_string())
+                }
+            }
+        }
+    }
+
+    let reason = match reason {
+        Some(reason) if !reason.is_empty() => reason,
+        _ => return Err("Ban reason couldn't be empty.".to_string()),
+    };
+    let minutes = match minutes {
+        Some(minutes) => minutes,
+        None => return Err("Ban duration is required, use `perm` for permanent bans.".to_string()),
+    };
+
+    Ok(BansSubcommand::Ban {
+        caller_discord_id: modal.user.id,
+        banning_player_login,
+        minutes,
+        reason,
+        severity: severity.unwrap_or(2),
+        address,
+        ban_hwid,
+    })
 }
 
-pub fn get_options(_options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<BansSubcommand, String> {
+pub fn get_options(_options: &[ResolvedOption], cmd: &CommandInteraction, config: &Config) -> Result<BansSubcommand, String> {
     if _options.len() != 1 {
         return Err("Invalid options length".to_string());
     }
@@ -181,17 +541,21 @@ pub fn get_options(_options: &[ResolvedOption], cmd: &CommandInteraction) -> Res
         "list" => parse_list_options(subcommand),
         "info" => parse_info_options(subcommand),
         "pardon" => parse_pardon_options(subcommand, cmd),
-        "ban" => parse_ban_options(subcommand, cmd),
+        "edit" => parse_edit_options(subcommand, cmd),
+        "ban" => parse_ban_options(subcommand, cmd, config),
+        "search" => parse_search_options(subcommand),
         _ => Err("Invalid subcommand".to_string())
     }
 }
 
-pub async fn execute(cmd: BansSubcommand, db: &PgDatabase, config: &Config) -> CreateInteractionResponseFollowup {
+pub async fn execute(cmd: BansSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations, searches: &SavedQueries<BanSearch>) -> CreateInteractionResponseFollowup {
     match cmd {
-        BansSubcommand::Ban { .. } => execute_ban_cmd(cmd, db, config).await,
+        BansSubcommand::Ban { .. } => execute_ban_cmd(cmd, db, config, confirmations).await,
         BansSubcommand::List(_) => execute_list_cmd(cmd, db).await,
-        BansSubcommand::Pardon { .. } => execute_pardon_cmd(cmd, db, config).await,
+        BansSubcommand::Pardon { .. } => execute_pardon_cmd(cmd, db, config, confirmations).await,
+        BansSubcommand::Edit { .. } => execute_edit_cmd(cmd, db, config, confirmations).await,
         BansSubcommand::Info(_) => execute_info_cmd(cmd, db).await,
+        BansSubcommand::Search { admin_login, search } => execute_search_cmd(admin_login, search, db, searches).await,
     }
 }
 
@@ -206,27 +570,41 @@ async fn execute_list_cmd(cmd: BansSubcommand, db: &PgDatabase) -> CreateInterac
         None => return create_response_with_content("No such player found.", true),
     };
 
-    match db.get_bans_list(&uuid).await {
-        Ok(bans) => {
-            let description = bans
-                .iter()
-                .map(format_short_ban_summary)
-                .collect::<Vec<String>>()
-                .join("\n");
+    match list_page(&uuid, &login, 0, db).await {
+        Ok((embed, components)) => CreateInteractionResponseFollowup::new().add_embed(embed).components(components).ephemeral(true),
+        Err(e) => create_response_with_content(&e, true),
+    }
+}
 
-            let embed = CreateEmbed::new()
-                .title(format!("Bans for `{}`", login))
-                .description(description)
-                .color(generate_random_colour())
-                .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
-            
-                CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
-        },
+pub static LIST_PAGE_PREFIX: &str = "bans_list";
+
+/// Builds a single page of player's bans list with navigation buttons.
+pub async fn list_page(uuid: &Uuid, login: &str, page: i64, db: &PgDatabase) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let (bans, total) = match tokio::try_join!(db.get_bans_page(uuid, page, PER_PAGE), db.count_bans(uuid)) {
+        Ok(result) => result,
         Err(e) => {
             error!("Error retrieving bans for {}. Error {}", login, e);
-            create_response_with_content("Failed to retrieve bans.", true)
+            return Err("Failed to retrieve bans.".to_string());
         }
+    };
+
+    let pages = pages_count(total);
+    let mut description = bans
+        .iter()
+        .map(format_short_ban_summary)
+        .collect::<Vec<String>>()
+        .join("\n");
+    if description.is_empty() {
+        description = "Nothing found.".to_string();
     }
+
+    let embed = CreateEmbed::new()
+        .title(format!("Bans for `{}`", login))
+        .description(description)
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Page {}/{} • VoidRelay By JerryImMouse", page + 1, pages)));
+
+    Ok((embed, page_buttons(LIST_PAGE_PREFIX, &uuid.to_string(), page, pages)))
 }
 
 async fn execute_info_cmd(cmd: BansSubcommand, db: &PgDatabase) -> CreateInteractionResponseFollowup {
@@ -237,12 +615,12 @@ async fn execute_info_cmd(cmd: BansSubcommand, db: &PgDatabase) -> CreateInterac
 
     match db.get_ban_by_id(id).await {
         Ok(Some(ban)) => {
-            let created_by = resolve_user_name(db, &ban.banning_admin).await;
-            let last_edited_by = if ban.last_edited_by_id.is_none() { None } else { Some(resolve_user_name(db, &ban.last_edited_by_id.unwrap()).await) };
-            let player = resolve_user_name(db, &ban.player_user_id).await;
-
+            let created_by = resolve_optional_user_name(db, ban.banning_admin.as_ref()).await;
+            let last_edited_by = if let Some(id) = ban.last_edited_by_id { Some(resolve_user_name(db, &id).await) } else { None };
+            let player = resolve_optional_user_name(db, ban.player_user_id.as_ref()).await;
+            let unbanned_by = if let Some(id) = ban.unbanning_admin { Some(resolve_user_name(db, &id).await) } else { None };
 
-            let fmt = format_ban_summary(&ban, &created_by, last_edited_by);
+            let fmt = format_ban_summary(&ban, &created_by, last_edited_by, unbanned_by, &Redaction::none());
             let embed = CreateEmbed::new()
                 .title(format!("Ban of `{}`", player))
                 .description(fmt)
@@ -261,69 +639,311 @@ async fn execute_info_cmd(cmd: BansSubcommand, db: &PgDatabase) -> CreateInterac
     }
 }
 
-async fn execute_pardon_cmd(cmd: BansSubcommand, db: &PgDatabase, config: &Config) -> CreateInteractionResponseFollowup {
+async fn execute_search_cmd(admin_login: Option<String>, mut search: BanSearch, db: &PgDatabase, searches: &SavedQueries<BanSearch>) -> CreateInteractionResponseFollowup {
+    if let Some(admin_login) = admin_login {
+        match get_user_id_by_login(&admin_login, db).await {
+            Some(uuid) => search.filter.banning_admin = Some(uuid),
+            None => return create_response_with_content("No such admin found.", true),
+        }
+    }
+
+    let key = searches.add(search.clone());
+    match search_page(&search, &key, 0, db).await {
+        Ok((embed, components)) => CreateInteractionResponseFollowup::new().add_embed(embed).components(components).ephemeral(true),
+        Err(e) => create_response_with_content(&e, true),
+    }
+}
+
+pub static SEARCH_PAGE_PREFIX: &str = "bans_search";
+// searches are kept in memory for their page buttons
+pub static SEARCH_TTL: StdDuration = StdDuration::from_secs(30 * 60);
+static MAX_NETWORK_PLAYERS: i64 = 15;
+
+/// Search filters with their human readable description.
+#[derive(Clone)]
+pub struct BanSearch {
+    filter: BanFilter,
+    summary: String,
+}
+
+/// Builds a single page of ban search results, `key` refers to the search in [`SavedQueries`].
+pub async fn search_page(search: &BanSearch, key: &str, page: i64, db: &PgDatabase) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let filter = &search.filter;
+    let (bans, total) = match tokio::try_join!(db.search_bans_page(filter, page, PER_PAGE), db.count_bans_search(filter)) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Error searching bans. Filter: {:?}. Error: {}", filter, e);
+            return Err("Failed to search bans.".to_string());
+        }
+    };
+
+    let pages = pages_count(total);
+    let mut description = bans
+        .iter()
+        .map(format_search_result)
+        .collect::<Vec<String>>()
+        .join("\n");
+    if description.is_empty() {
+        description = "Nothing found.".to_string();
+    }
+    if !search.summary.is_empty() {
+        description = format!("{}\n\n{}", search.summary, description);
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title("Ban search")
+        .description(description)
+        .colour(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Page {}/{} • {} total • VoidRelay By JerryImMouse", page + 1, pages, total)));
+
+    // players can be inside a network without being banned
+    if let Some(address) = &filter.address {
+        let players = match db.get_players_in_network(address, MAX_NETWORK_PLAYERS).await {
+            Ok(players) => players,
+            Err(e) => {
+                error!("Error searching players in network {}. Error: {}", address, e);
+                return Err("Failed to search players.".to_string());
+            }
+        };
+
+        let mut value = players.iter()
+            .map(|p| format!("`{}` 📍 `{}` • <t:{}:R>", p.last_seen_user_name, p.last_seen_address, p.last_seen_time.timestamp()))
+            .collect::<Vec<String>>()
+            .join("\n");
+        if value.is_empty() {
+            value = "Nothing found.".to_string();
+        }
+        embed = embed.field(format!("Players in network (first {})", MAX_NETWORK_PLAYERS), shorten_message(&value, MAX_FIELD_LEN), false);
+    }
+
+    Ok((embed, page_buttons(SEARCH_PAGE_PREFIX, key, page, pages)))
+}
+
+fn format_search_result(ban: &BanSearchResult) -> String {
+    format!(
+        "**{}**. {} `{}` <t:{}:d> 📍 `{}` {}",
+        ban.server_ban_id,
+        ban_status(ban.expiration_time, ban.pardoned),
+        ban.user_name.as_deref().unwrap_or("Unknown"),
+        ban.ban_time.timestamp(),
+        ban.address.as_ref().map(format_network).unwrap_or("—".to_string()),
+        shorten_message(&ban.reason, SHORT_MSG_LEN_SYMBOLS),
+    )
+}
+
+/// Short badge of ban state.
+pub fn ban_status(expiration_time: Option<DateTime<Utc>>, pardoned: bool) -> &'static str {
+    if pardoned {
+        "🕊️ Pardoned"
+    } else if expiration_time.is_some_and(|t| t <= Utc::now()) {
+        "⌛ Expired"
+    } else {
+        "🔴 Active"
+    }
+}
+
+async fn execute_pardon_cmd(cmd: BansSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations) -> CreateInteractionResponseFollowup {
     let (caller, id) = match cmd {
-        BansSubcommand::Pardon {caller_discord_id, id} => (caller_discord_id.to_string(), id),
+        BansSubcommand::Pardon {caller_discord_id, id} => (caller_discord_id, id),
         _ => panic!("Invalid subcommand passed")
     };
 
-    let auth_client = DiscordApiClient::new(config.auth_url(), config.auth_token());
-    let auth_client = auth_client;
-    if let Err(e) = auth_client {
-        error!("Error creating auth client: {e}");
-        return create_response_with_content("Unable to setup authorization client.", true);
+    let clients = prepare_clients(config);
+    if let Err(e) = clients {
+        return create_response_with_content(&e, true);
     }
-    let auth_client = auth_client.unwrap();
-    
-    let admin_uuid = auth_client.uuid(&caller).await;
-    let admin_uuid = match admin_uuid {
-        Some(uuid) => uuid,
-        None => return create_response_with_content("You're probably unauthorized to perform this action.", true),
+    let (_, discord_api) = clients.unwrap();
+
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
     };
 
-    let admin_name = match db.get_login_by_uuid(&admin_uuid).await {
-        Ok(Some(login)) => login,
-        Ok(None) => return create_response_with_content("Unable to fetch admin name.", true),
-        Err(err) => {
-            warn!("Failed to fetch user name for {}: {}", admin_uuid, err);
-            return create_response_with_content("Unable to fetch admin name.", true)
+    let ban = match db.get_ban_by_id(id as i32).await {
+        Ok(Some(ban)) => ban,
+        Ok(None) => return create_response_with_content(&format!("Ban with id: {} is not found", id), true),
+        Err(e) => {
+            error!("Error retrieving ban by id: {}. Error: {}", id, e);
+            return create_response_with_content("Error happened retrieving ban.", true);
         }
     };
-
-    let ss14_client = SS14ApiClient::new(config.api_url(), config.server_token());
-    if let Err(e) = ss14_client {
-        error!("Error creating ss14 client: {e}");
-        return create_response_with_content("Unable to setup ss14 client.", true);
+    if ban.unban_time.is_some() {
+        return create_response_with_content(&format!("Ban with id: {} is already pardoned", id), true);
     }
-    let ss14_client = ss14_client.unwrap();
+    let player = resolve_optional_user_name(db, ban.player_user_id.as_ref()).await;
 
     let actor = SS14ApiActor::from((admin_uuid.to_string().as_str(), admin_name.as_str()));
     let pardon_cmd = PardonRequest::new(id as i32, actor);
 
-    let result = ss14_client.pardon(pardon_cmd).await;
+    let embed = CreateEmbed::new()
+        .title(format!("Pardon ban `{}` of `{}`?", id, player))
+        .description(format!(
+            "👤 **Player:** {}\n🆔 **UUID:** {}\n📅 **Ban Time:** {}\n\n📝 **Reason:**\n{}",
+            player, ban.player_user_id.map(|id| id.to_string()).unwrap_or("Unknown".to_string()), ban.ban_time, ban.reason
+        ))
+        .colour(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Expires in {} seconds • VoidRelay By JerryImMouse", confirmations.timeout().as_secs())));
 
-    match result {
-        Ok(_) => create_response_with_content(&format!("Successfully pardoned ban with id: {}", id), true),
+    let audit = AuditEntry::new(caller, "bans.pardon", player)
+        .admin(admin_uuid, &admin_name)
+        .param("ban_id", id);
+
+    let buttons = confirmations.add(caller, PendingAction::Pardon { id: id as i32, request: pardon_cmd, audit });
+    CreateInteractionResponseFollowup::new().add_embed(embed).components(buttons).ephemeral(true)
+}
+
+/// Sends pardon confirmed by admin to the game server.
+pub async fn execute_confirmed_pardon(id: i32, request: PardonRequest, audit: AuditEntry, db: &PgDatabase, config: &Config, http: &Http) -> String {
+    let ss14_client = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return "Unable to setup ss14 client.".to_string();
+        }
+    };
+
+    match ss14_client.pardon(request).await {
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            format!("Successfully pardoned ban with id: {}", id)
+        },
         Err(e) => {
             error!("Unable to pardon ban. Id: {id}. Error: {e}");
             if let Error::SS14ApiError(e) = e {
-                create_response_with_content(&format!("Error during pardon: {}", e), true)
+                format!("Error during pardon: {}", e)
             } else {
-                create_response_with_content("Error occured during ban pardon.", true)
+                "Error occured during ban pardon.".to_string()
             }
         }
     }
 }
 
-async fn execute_ban_cmd(cmd: BansSubcommand, db: &PgDatabase, config: &Config) -> CreateInteractionResponseFollowup {
-    let (caller, player_login, minutes, reason, severity) = match cmd {
+async fn execute_edit_cmd(cmd: BansSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations) -> CreateInteractionResponseFollowup {
+    let (caller, id, minutes, reason) = match cmd {
+        BansSubcommand::Edit { caller_discord_id, id, minutes, reason } => (caller_discord_id, id, minutes, reason),
+        _ => panic!("Invalid subcommand passed")
+    };
+
+    let clients = prepare_clients(config);
+    if let Err(e) = clients {
+        return create_response_with_content(&e, true);
+    }
+    let (_, discord_api) = clients.unwrap();
+
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    let ban = match db.get_ban_by_id(id).await {
+        Ok(Some(ban)) => ban,
+        Ok(None) => return create_response_with_content(&format!("Ban with id: {} is not found", id), true),
+        Err(e) => {
+            error!("Error retrieving ban by id: {}. Error: {}", id, e);
+            return create_response_with_content("Error happened retrieving ban.", true);
+        }
+    };
+    if ban.unban_time.is_some() {
+        return create_response_with_content(&format!("Ban with id: {} is already pardoned", id), true);
+    }
+
+    if let Some(minutes) = minutes {
+        if let Err(e) = check_duration_limit(&admin_uuid, minutes, db, config).await {
+            return create_response_with_content(&e, true);
+        }
+    }
+
+    let player = resolve_optional_user_name(db, ban.player_user_id.as_ref()).await;
+    let mut audit = AuditEntry::new(caller, "bans.edit", &player)
+        .admin(admin_uuid, &admin_name)
+        .param("ban_id", id);
+    let mut description = format!("👤 **Player:** {}\n📅 **Ban Time:** {}\n", player, ban.ban_time);
+    if let Some(minutes) = minutes {
+        audit = audit.param("duration", format_duration(minutes));
+        description.push_str(&format!("⏳ **New Duration:** {}\n", format_duration(minutes)));
+    }
+    if let Some(reason) = &reason {
+        audit = audit.param("reason", reason);
+        description.push_str(&format!("\n📝 **New Reason:**\n{}", reason));
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!("Edit ban `{}` of `{}`?", id, player))
+        .description(description)
+        .colour(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Expires in {} seconds • VoidRelay By JerryImMouse", confirmations.timeout().as_secs())));
+
+    let edit = ServerBanEdit {
+        edited_by: admin_uuid,
+        reason: reason.clone(),
+        // duration is counted from the original ban time, as the game does it
+        expiration_time: minutes.map(|m| if m > 0 { Some(ban.ban_time + Duration::minutes(m)) } else { None }),
+    };
+    let actor = SS14ApiActor::new(admin_uuid, &admin_name);
+    let request = EditBanRequest::new(id, reason, minutes.map(|m| m as i32), actor);
+
+    let buttons = confirmations.add(caller, PendingAction::EditBan { id, request, edit, audit });
+    CreateInteractionResponseFollowup::new().add_embed(embed).components(buttons).ephemeral(true)
+}
+
+pub async fn execute_confirmed_edit(id: i32, request: EditBanRequest, edit: ServerBanEdit, audit: AuditEntry, db: &PgDatabase, config: &Config, http: &Http) -> String {
+    let ss14_client = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return "Unable to setup ss14 client.".to_string();
+        }
+    };
+
+    // ban could be removed or pardoned while confirmation was pending
+    match db.get_ban_by_id(id).await {
+        Ok(Some(ban)) if ban.unban_time.is_some() => return format!("Ban with id: {} is already pardoned", id),
+        Ok(Some(_)) => {},
+        Ok(None) => return format!("Ban with id: {} is not found", id),
+        Err(e) => {
+            error!("Error retrieving ban by id: {}. Error: {}", id, e);
+            return "Error happened retrieving ban.".to_string();
+        }
+    }
+
+    let edited_by = edit.edited_by;
+    let result = match ss14_client.edit_ban(request).await {
+        Err(Error::SS14ApiUnsupported(_)) => {
+            info!("SS14 api doesn't support ban edits, writing edit of ban {} to database.", id);
+            db.edit_ban(id, edit).await
+        },
+        Ok(_) => db.set_ban_last_edited(id, &edited_by).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(0) => format!("Ban with id: {} is not found", id),
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            format!("Successfully edited ban with id: {}", id)
+        },
+        Err(e) => {
+            error!("Unable to edit ban. Id: {id}. Error: {e}");
+            if let Error::SS14ApiError(e) = e {
+                format!("Error during ban edit: {}", e)
+            } else {
+                "Error occured during ban edit.".to_string()
+            }
+        }
+    }
+}
+
+async fn execute_ban_cmd(cmd: BansSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations) -> CreateInteractionResponseFollowup {
+    let (caller, player_login, minutes, reason, severity, address, ban_hwid) = match cmd {
         BansSubcommand::Ban {
             caller_discord_id,
             banning_player_login,
             minutes,
             reason,
-            severity
-        } => (caller_discord_id, banning_player_login, minutes, reason, severity),
+            severity,
+            address,
+            ban_hwid,
+        } => (caller_discord_id, banning_player_login, minutes, reason, severity, address, ban_hwid),
         _ => panic!("Invalid subcommand passed")
     };
 
@@ -331,21 +951,11 @@ async fn execute_ban_cmd(cmd: BansSubcommand, db: &PgDatabase, config: &Config)
     if let Err(e) = clients {
         return create_response_with_content(&e, true);
     }
-    let (ss14_api, discord_api) = clients.unwrap();
+    let (_, discord_api) = clients.unwrap();
 
-    let admin_uuid = discord_api.uuid(&caller.get().to_string()).await;
-    let admin_uuid = match admin_uuid {
-        Some(uuid) => uuid,
-        None => return create_response_with_content("You're probably unauthorized to perform this action.", true),
-    };
-
-    let admin_name = match db.get_login_by_uuid(&admin_uuid).await {
-        Ok(Some(login)) => login,
-        Ok(None) => return create_response_with_content("Unable to fetch admin name.", true),
-        Err(err) => {
-            warn!("Failed to fetch user name for {}: {}", admin_uuid, err);
-            return create_response_with_content("Unable to fetch admin name.", true)
-        }
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
     };
 
     let banning_uuid = match get_user_id_by_login(&player_login, db).await {
@@ -357,52 +967,111 @@ async fn execute_ban_cmd(cmd: BansSubcommand, db: &PgDatabase, config: &Config)
     };
 
     let actor = SS14ApiActor::from((admin_uuid.to_string().as_str(), admin_name.as_str()));
-    let ban_cmd = BanRequest::new(player_login.to_owned(),banning_uuid, reason, minutes as i32, severity, actor);
+    if let Err(e) = check_duration_limit(&admin_uuid, minutes, db, config).await {
+        return create_response_with_content(&e, true);
+    }
+
+    // shown to admin, so it's clear what is going to be banned
+    let hwid = if ban_hwid {
+        match db.get_player_profile(&banning_uuid).await {
+            Ok(Some(PlayerProfile { last_seen_hwid: Some(hwid), .. })) if !hwid.is_empty() => Some(format_hwid(&hwid)),
+            Ok(_) => return create_response_with_content("Player has no known HWID to ban.", true),
+            Err(e) => {
+                error!("Error retrieving profile of {}: {}", player_login, e);
+                return create_response_with_content("Failed to retrieve player's HWID.", true);
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut description = format!(
+        "👤 **Player:** {}\n🆔 **UUID:** {}\n⏳ **Duration:** {}\n⚠️ **Severity:** {}\n",
+        player_login, banning_uuid, format_duration(minutes), severity
+    );
+    if let Some(address) = &address {
+        description.push_str(&format!("📍 **Address:** {}\n", format_network(address)));
+    }
+    if let Some(hwid) = &hwid {
+        description.push_str(&format!("🖥️ **HWID:** {}\n", hwid));
+    }
+    description.push_str(&format!("\n📝 **Reason:**\n{}", reason));
 
-    let result = ss14_api.ban(ban_cmd).await;
+    let embed = CreateEmbed::new()
+        .title(format!("Ban `{}`?", player_login))
+        .description(description)
+        .colour(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Expires in {} seconds • VoidRelay By JerryImMouse", confirmations.timeout().as_secs())));
 
-    match result {
-        Ok(_) => create_response_with_content(&format!("Successfully banned player: `{}`", player_login), true),
+    let mut audit = AuditEntry::new(caller, "bans.ban", &player_login)
+        .admin(admin_uuid, &admin_name)
+        .param("duration", format_duration(minutes))
+        .param("severity", severity)
+        .param("reason", &reason);
+    if let Some(address) = &address {
+        audit = audit.param("address", format_network(address));
+    }
+    if let Some(hwid) = &hwid {
+        audit = audit.param("hwid", hwid);
+    }
+
+    let mut ban_cmd = BanRequest::new(player_login.to_owned(),banning_uuid, reason, minutes as i32, severity, actor).ban_hwid(ban_hwid);
+    if let Some(address) = address {
+        ban_cmd = ban_cmd.address(address);
+    }
+
+    let buttons = confirmations.add(caller, PendingAction::Ban { login: player_login, request: ban_cmd, audit });
+    CreateInteractionResponseFollowup::new().add_embed(embed).components(buttons).ephemeral(true)
+}
+
+/// Sends ban confirmed by admin to the game server.
+pub async fn execute_confirmed_ban(player_login: String, request: BanRequest, audit: AuditEntry, db: &PgDatabase, config: &Config, http: &Http) -> String {
+    let ss14_api = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return "Unable to setup ss14 client.".to_string();
+        }
+    };
+
+    match ss14_api.ban(request).await {
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            format!("Successfully banned player: `{}`", player_login)
+        },
         Err(e) => {
             error!("Unable to ban player: {player_login}. Error: {e}");
             if let Error::SS14ApiError(e) = e {
-                create_response_with_content(&format!("Error during ban: {}", e), true)
+                format!("Error during ban: {}", e)
             } else {
-                create_response_with_content("Error occured during ban.", true)
+                "Error occured during ban.".to_string()
             }
         }
     }
 }
 
 fn format_short_ban_summary(ban: &ServerBanShort) -> String {
-    let short_msg = if ban.reason.chars().count() <= SHORT_MSG_LEN_SYMBOLS {
-        ban.reason.clone()
-    } else {
-        let end_index = ban
-            .reason
-            .char_indices()
-            .nth(SHORT_MSG_LEN_SYMBOLS)
-            .map(|(idx, _)| idx)
-            .unwrap_or_else(|| ban.reason.len());
-        format!("{}...", &ban.reason[..end_index])
-    };
-
-    format!("**{}**. {}", ban.server_ban_id, short_msg)
+    format!("**{}**. {} {}", ban.server_ban_id, ban_status(ban.expiration_time, ban.pardoned), shorten_message(&ban.reason, SHORT_MSG_LEN_SYMBOLS))
 }
 
-fn format_ban_summary(ban: &ServerBan, created_by: &str, last_edited_by: Option<String>) -> String {
+/// Full ban description, `redaction` hides details not meant for the audience.
+pub fn format_ban_summary(ban: &ServerBan, created_by: &str, last_edited_by: Option<String>, unbanned_by: Option<String>, redaction: &Redaction) -> String {
     let mut formatted = format!(
         r#"🔒 **Ban ID:** {}
+🔖 **Status:** {}
 📅 **Ban Time:** {}
-📍 **Address:** {}
-✍️ **Banning Admin:** {}
 "#,
         ban.server_ban_id,
+        ban_status(ban.expiration_time, ban.unban_time.is_some()),
         ban.ban_time,
-        ban.address,
-        created_by,
     );
 
+    if let Some(address) = ban.address.as_ref().filter(|_| !redaction.address) {
+        formatted.push_str(&format!("📍 **Address:** {}\n", format_network(address)));
+    }
+
+    formatted.push_str(&format!("✍️ **Banning Admin:** {}\n", created_by));
+
     // Optional Fields
     if let Some(expiration_time) = ban.expiration_time {
         formatted.push_str(&format!("⏳ **Expiration Time:** {}\n", expiration_time));
@@ -410,8 +1079,8 @@ fn format_ban_summary(ban: &ServerBan, created_by: &str, last_edited_by: Option<
         formatted.push_str("⏳ **Expiration Time:** Never\n");
     }
 
-    if !ban.hwid.is_empty() {
-        formatted.push_str(&format!("  **HWID:** {}\n", String::from_utf8_lossy(&ban.hwid)));
+    if let Some(hwid) = ban.hwid.as_ref().filter(|hwid| !hwid.is_empty() && !redaction.hwid) {
+        formatted.push_str(&format!("🖥️ **HWID:** {}\n", format_hwid(hwid)));
     }
 
     if let Some(last_edited_at) = ban.last_edited_at {
@@ -426,6 +1095,11 @@ fn format_ban_summary(ban: &ServerBan, created_by: &str, last_edited_by: Option<
         formatted.push_str(&format!("✨ **Round ID:** {}\n", round_id));
     }
 
+    if let Some(unban_time) = ban.unban_time {
+        formatted.push_str(&format!("🕊️ **Pardoned By:** {}\n", unbanned_by.as_deref().unwrap_or("Unknown")));
+        formatted.push_str(&format!("📅 **Pardon Time:** {}\n", unban_time));
+    }
+
     formatted.push_str(if ban.auto_delete {
         "**🗑️ Auto Delete:** Yes\n"
     } else {
@@ -437,24 +1111,6 @@ fn format_ban_summary(ban: &ServerBan, created_by: &str, last_edited_by: Option<
     formatted
 }
 
-fn prepare_clients(config: &Config) -> Result<(SS14ApiClient, DiscordApiClient), String> {
-    let auth_client = DiscordApiClient::new(config.auth_url(), config.auth_token());
-    if let Err(e) = auth_client {
-        error!("Error creating auth client: {e}");
-        return Err("Unable to setup authorization client.".to_string());
-    }
-    let auth_client = auth_client.unwrap();
-    
-    let ss14_client = SS14ApiClient::new(config.api_url(), config.server_token());
-    if let Err(e) = ss14_client {
-        error!("Error creating ss14 client: {e}");
-        return Err("Unable to setup ss14 client.".to_string());
-    }
-    let ss14_client = ss14_client.unwrap();
-
-    Ok((ss14_client, auth_client))
-}
-
 pub enum BansSubcommand {
     List(String),
     Info(i32),
@@ -462,11 +1118,23 @@ pub enum BansSubcommand {
         caller_discord_id: UserId,
         id: i64
     },
+    Edit {
+        caller_discord_id: UserId,
+        id: i32,
+        minutes: Option<i64>,
+        reason: Option<String>,
+    },
     Ban {
         caller_discord_id: UserId,
         banning_player_login: String,
         minutes: i64,
         reason: String,
         severity: u16,
-    }
+        address: Option<IpNetwork>,
+        ban_hwid: bool,
+    },
+    Search {
+        admin_login: Option<String>,
+        search: BanSearch,
+    },
 }
\ No newline at end of file
@@ -0,0 +1,212 @@
+use chrono::{Duration, NaiveDate};
+use log::error;
+use serenity::all::{CommandOptionType, CreateActionRow, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, ResolvedOption, ResolvedValue};
+use uuid::Uuid;
+
+use crate::{bot::{create_response_with_content, pagination::{page_buttons, pages_count, PER_PAGE}, utilities::{day_start, format_date, format_hwid, generate_random_colour, get_user_id_by_login, parse_date, shorten_message}}, database::{ConnectionFilter, ConnectionLog, PgDatabase}};
+
+static SHORT_HWID_LEN_SYMBOLS: usize = 16;
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("connections")
+        .description("Searches connection log of a player")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "login",
+                "In-Game login",
+            )
+            .set_autocomplete(true)
+            .required(true),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "from",
+                "First day to show, e.g. `2025-01-31`",
+            )
+            .required(false),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "to",
+                "Last day to show, e.g. `2025-02-28`",
+            )
+            .required(false),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "denied_only",
+                "Show only denied connection attempts",
+            )
+            .required(false),
+        )
+}
+
+pub fn get_options(options: &[ResolvedOption]) -> Result<ConnectionsCommand, String> {
+    let mut login: Option<String> = None;
+    let mut from: Option<NaiveDate> = None;
+    let mut to: Option<NaiveDate> = None;
+    let mut denied_only = false;
+
+    for option in options {
+        match (option.name, &option.value) {
+            ("login", ResolvedValue::String(l)) => login = Some(l.to_string()),
+            ("from", ResolvedValue::String(d)) => from = Some(parse_date(d)?),
+            ("to", ResolvedValue::String(d)) => to = Some(parse_date(d)?),
+            ("denied_only", ResolvedValue::Boolean(d)) => denied_only = *d,
+            _ => return Err("Invalid options passed".to_string())
+        }
+    }
+
+    let login = match login {
+        Some(login) => login,
+        None => return Err("Invalid or missing 'login' option".to_string()),
+    };
+
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err("`from` date is after `to` date.".to_string());
+        }
+    }
+
+    Ok(ConnectionsCommand { login, from, to, denied_only })
+}
+
+pub async fn execute(cmd: ConnectionsCommand, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+    let uuid = match get_user_id_by_login(&cmd.login, db).await {
+        Some(uuid) => uuid,
+        None => return create_response_with_content("No such player found.", true),
+    };
+
+    let query = ConnectionsQuery { user_id: uuid, from: cmd.from, to: cmd.to, denied_only: cmd.denied_only };
+    match list_page(&query, &cmd.login, 0, db).await {
+        Ok((embed, components)) => CreateInteractionResponseFollowup::new().add_embed(embed).components(components).ephemeral(true),
+        Err(e) => create_response_with_content(&e, true),
+    }
+}
+
+pub static LIST_PAGE_PREFIX: &str = "connections_list";
+
+/// Search parameters kept in page buttons, so navigation doesn't lose them.
+pub struct ConnectionsQuery {
+    user_id: Uuid,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    denied_only: bool,
+}
+
+impl ConnectionsQuery {
+    /// Page key in `uuid;from;to;denied` form, dates are empty if not set.
+    fn to_key(&self) -> String {
+        let date = |d: Option<NaiveDate>| d.as_ref().map(format_date).unwrap_or_default();
+        format!("{};{};{};{}", self.user_id, date(self.from), date(self.to), self.denied_only as u8)
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        let mut parts = key.split(';');
+        let user_id = Uuid::parse_str(parts.next()?).ok()?;
+        let date = |d: &str| if d.is_empty() { Ok(None) } else { parse_date(d).map(Some) };
+        let from = date(parts.next()?).ok()?;
+        let to = date(parts.next()?).ok()?;
+        let denied_only = parts.next()? == "1";
+
+        Some(Self { user_id, from, to, denied_only })
+    }
+
+    pub fn user_id(&self) -> &Uuid {
+        &self.user_id
+    }
+
+    fn filter(&self) -> ConnectionFilter {
+        ConnectionFilter {
+            user_id: self.user_id,
+            from: self.from.map(day_start),
+            // whole last day is included
+            to: self.to.map(|d| day_start(d) + Duration::days(1)),
+            denied_only: self.denied_only,
+        }
+    }
+}
+
+/// Builds a single page of player's connections with navigation buttons.
+pub async fn list_page(query: &ConnectionsQuery, login: &str, page: i64, db: &PgDatabase) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let filter = query.filter();
+    let (connections, total) = match tokio::try_join!(db.get_connections_page(&filter, page, PER_PAGE), db.count_connections(&filter)) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Error retrieving connections of {}. Error {}", login, e);
+            return Err("Failed to retrieve connections.".to_string());
+        }
+    };
+
+    let pages = pages_count(total);
+    let mut description = connections
+        .iter()
+        .map(format_connection)
+        .collect::<Vec<String>>()
+        .join("\n");
+    if description.is_empty() {
+        description = "Nothing found.".to_string();
+    }
+
+    let title = if query.denied_only {
+        format!("Denied connections of `{}`", login)
+    } else {
+        format!("Connections of `{}`", login)
+    };
+
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Page {}/{} • {} total • VoidRelay By JerryImMouse", page + 1, pages, total)));
+
+    Ok((embed, page_buttons(LIST_PAGE_PREFIX, &query.to_key(), page, pages)))
+}
+
+fn format_connection(connection: &ConnectionLog) -> String {
+    let mut formatted = format!(
+        "**{}**. <t:{}:f> `{}` 📍 `{}`",
+        connection.connection_log_id,
+        connection.time.timestamp(),
+        connection.user_name,
+        connection.address,
+    );
+
+    if let Some(hwid) = connection.hwid.as_ref().filter(|hwid| !hwid.is_empty()) {
+        formatted.push_str(&format!(" 🖥️ `{}`", shorten_message(&format_hwid(hwid), SHORT_HWID_LEN_SYMBOLS)));
+    }
+
+    if !connection.server_name.is_empty() {
+        formatted.push_str(&format!(" 🌐 {}", connection.server_name));
+    }
+
+    if let Some(denied) = connection.denied {
+        formatted.push_str(&format!(" ❌ {}", format_deny_reason(denied)));
+    }
+
+    formatted
+}
+
+// ConnectionDenyReason of SS14
+fn format_deny_reason(reason: i32) -> String {
+    match reason {
+        0 => "Ban".to_string(),
+        1 => "Whitelist".to_string(),
+        2 => "Full".to_string(),
+        3 => "Panic Bunker".to_string(),
+        4 => "Baby Jail".to_string(),
+        5 => "IP Checks".to_string(),
+        other => format!("Denied ({})", other),
+    }
+}
+
+pub struct ConnectionsCommand {
+    login: String,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    denied_only: bool,
+}
@@ -0,0 +1,167 @@
+use log::error;
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, Http, ResolvedOption, ResolvedValue, UserId};
+
+use crate::{api::ss14client::{GameRuleRequest, SS14ApiActor, SS14ApiClient}, bot::{audit::{self, AuditEntry}, create_response_with_content, utilities::{generate_random_colour, prepare_clients, resolve_admin}}, config::Config, database::PgDatabase, error::Error};
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("gamerules")
+        .description("Manages game rules of current round")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "list",
+            "Lists active game rules",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "add",
+                "Adds and starts game rule",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "rule",
+                    "Game rule ID",
+                )
+                .set_autocomplete(true)
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "end",
+                "Ends active game rule",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "rule",
+                    "Game rule ID",
+                )
+                .set_autocomplete(true)
+                .required(true),
+            ),
+        )
+}
+
+fn parse_rule_option(opt: &ResolvedOption) -> Result<String, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        if let Some(ResolvedOption { value: ResolvedValue::String(rule), .. }) = suboptions.first() {
+            return Ok(rule.to_string());
+        }
+    }
+    Err("Invalid or missing 'rule' option".to_string())
+}
+
+pub fn get_options(options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<GameRulesSubcommand, String> {
+    if options.len() != 1 {
+        return Err("Invalid options count".to_string());
+    }
+
+    let caller_discord_id = cmd.user.id;
+    let subcommand = options.first().unwrap();
+    match subcommand.name {
+        "list" => Ok(GameRulesSubcommand::List),
+        "add" => parse_rule_option(subcommand).map(|rule| GameRulesSubcommand::Add { caller_discord_id, rule }),
+        "end" => parse_rule_option(subcommand).map(|rule| GameRulesSubcommand::End { caller_discord_id, rule }),
+        _ => Err("Invalid subcommand.".to_string())
+    }
+}
+
+pub async fn execute(cmd: GameRulesSubcommand, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
+    match cmd {
+        GameRulesSubcommand::List => execute_list_cmd(config).await,
+        GameRulesSubcommand::Add { caller_discord_id, rule } => execute_rule_cmd(caller_discord_id, rule, true, db, config, http).await,
+        GameRulesSubcommand::End { caller_discord_id, rule } => execute_rule_cmd(caller_discord_id, rule, false, db, config, http).await,
+    }
+}
+
+async fn execute_list_cmd(config: &Config) -> CreateInteractionResponseFollowup {
+    let ss14_client = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return create_response_with_content("Unable to setup ss14 client.", true);
+        }
+    };
+
+    let info = match ss14_client.info().await {
+        Ok(info) => info,
+        Err(e) => {
+            error!("Error fetching round info: {e}");
+            return create_response_with_content(&game_rules_error("list", e), true);
+        }
+    };
+
+    let mut description = info.active_game_rules.iter()
+        .map(|rule| format!("• `{}`", rule))
+        .collect::<Vec<String>>()
+        .join("\n");
+    if description.is_empty() {
+        description = "Nothing found.".to_string();
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!("Active game rules of round {}", info.round_id))
+        .description(description)
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+    CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
+}
+
+async fn execute_rule_cmd(caller: UserId, rule: String, add: bool, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
+    let clients = prepare_clients(config);
+    if let Err(e) = clients {
+        return create_response_with_content(&e, true);
+    }
+    let (ss14_api, discord_api) = clients.unwrap();
+
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    let action = if add { "gamerules.add" } else { "gamerules.end" };
+    let audit = AuditEntry::new(caller, action, &rule).admin(admin_uuid, &admin_name);
+    let request = GameRuleRequest::new(rule.clone(), SS14ApiActor::new(admin_uuid, &admin_name));
+
+    let result = if add {
+        ss14_api.add_game_rule(request).await
+    } else {
+        ss14_api.end_game_rule(request).await
+    };
+
+    match result {
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            let done = if add { "added" } else { "ended" };
+            create_response_with_content(&format!("Successfully {} game rule `{}`", done, rule), true)
+        },
+        Err(e) => {
+            error!("Unable to change game rule {}. Error: {e}", rule);
+            create_response_with_content(&game_rules_error(if add { "add" } else { "end" }, e), true)
+        }
+    }
+}
+
+fn game_rules_error(action: &str, e: Error) -> String {
+    if let Error::SS14ApiError(e) = e {
+        format!("Error during game rule {}: {}", action, e)
+    } else {
+        format!("Error occured during game rule {}.", action)
+    }
+}
+
+pub enum GameRulesSubcommand {
+    List,
+    Add {
+        caller_discord_id: UserId,
+        rule: String,
+    },
+    End {
+        caller_discord_id: UserId,
+        rule: String,
+    },
+}
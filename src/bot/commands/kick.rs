@@ -0,0 +1,100 @@
+use log::error;
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, CreateInteractionResponseFollowup, Http, ResolvedOption, ResolvedValue, UserId};
+
+use crate::{api::ss14client::{KickRequest, SS14ApiActor}, bot::{audit::{self, AuditEntry}, create_response_with_content, utilities::{get_user_id_by_login, prepare_clients, resolve_admin}}, config::Config, database::PgDatabase, error::Error};
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("kick")
+        .description("Kicks player from SS14 server")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "login",
+                "In-Game Login",
+            )
+            .set_autocomplete(true)
+            .required(true),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "reason",
+                "Reason to kick for",
+            )
+            .required(false),
+        )
+}
+
+pub fn get_options(options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<KickCommand, String> {
+    let caller_discord_id = cmd.user.id;
+
+    let mut login: Option<String> = None;
+    let mut reason: Option<String> = None;
+
+    for option in options {
+        match (option.name, &option.value) {
+            ("login", ResolvedValue::String(l)) => login = Some(l.to_string()),
+            ("reason", ResolvedValue::String(r)) => reason = Some(r.to_string()),
+            _ => return Err("Invalid options passed".to_string())
+        }
+    }
+
+    let login = match login {
+        Some(login) => login,
+        None => return Err("Kicking player couldn't be none".to_string()),
+    };
+
+    Ok(KickCommand {
+        caller_discord_id,
+        login,
+        reason: reason.unwrap_or("No reason provided".to_string()),
+    })
+}
+
+pub async fn execute(cmd: KickCommand, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
+    let clients = prepare_clients(config);
+    if let Err(e) = clients {
+        return create_response_with_content(&e, true);
+    }
+    let (ss14_api, discord_api) = clients.unwrap();
+
+    let (admin_uuid, admin_name) = match resolve_admin(cmd.caller_discord_id, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    let player_uuid = match get_user_id_by_login(&cmd.login, db).await {
+        Some(uuid) => uuid,
+        None => {
+            error!("Unable to find uuid of user: {}", cmd.login);
+            return create_response_with_content("Unable to find kicking player.", true);
+        }
+    };
+
+    let actor = SS14ApiActor::new(admin_uuid, &admin_name);
+    let audit = AuditEntry::new(cmd.caller_discord_id, "kick", &cmd.login)
+        .admin(admin_uuid, &admin_name)
+        .param("reason", &cmd.reason);
+    let kick_cmd = KickRequest::new(player_uuid, cmd.reason, actor);
+
+    match ss14_api.kick(kick_cmd).await {
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            create_response_with_content(&format!("Successfully kicked player: `{}`", cmd.login), true)
+        },
+        Err(e) => {
+            error!("Unable to kick player: {}. Error: {e}", cmd.login);
+            if let Error::SS14ApiError(e) = e {
+                create_response_with_content(&format!("Error during kick: {}", e), true)
+            } else {
+                create_response_with_content("Error occured during kick.", true)
+            }
+        }
+    }
+}
+
+pub struct KickCommand {
+    caller_discord_id: UserId,
+    login: String,
+    reason: String,
+}
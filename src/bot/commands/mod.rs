@@ -1,13 +1,35 @@
 pub mod whitelist;
 pub mod notes;
 pub mod ban;
+pub mod kick;
+pub mod roleban;
+pub mod whois;
+pub mod achievements;
+pub mod audit;
+pub mod status;
+pub mod round;
+pub mod gamerules;
+pub mod player;
+pub mod connections;
+pub mod alts;
 
 use std::str::FromStr;
 
 pub enum DiscordCommandType {
     Whitelist,
     Notes,
-    Ban
+    Ban,
+    Kick,
+    RoleBan,
+    Whois,
+    Achievements,
+    Audit,
+    Status,
+    Round,
+    GameRules,
+    Player,
+    Connections,
+    Alts,
     // todo
 }
 
@@ -19,7 +41,62 @@ impl FromStr for DiscordCommandType {
             "whitelist" => Ok(Self::Whitelist),
             "notes" => Ok(Self::Notes),
             "bans" => Ok(Self::Ban),
+            "kick" => Ok(Self::Kick),
+            "rolebans" => Ok(Self::RoleBan),
+            "whois" => Ok(Self::Whois),
+            "achievements" => Ok(Self::Achievements),
+            "audit" => Ok(Self::Audit),
+            "status" => Ok(Self::Status),
+            "round" => Ok(Self::Round),
+            "gamerules" => Ok(Self::GameRules),
+            "player" => Ok(Self::Player),
+            "connections" => Ok(Self::Connections),
+            "alts" => Ok(Self::Alts),
             _ => Err(())
         }
     }
-}
\ No newline at end of file
+}
+
+pub enum DiscordComponentType {
+    BansList,
+    NotesList,
+    RoleBansList,
+    BansSearch,
+    ConnectionsList,
+    Confirm,
+    Cancel,
+}
+
+// assume str as custom_id prefix
+impl FromStr for DiscordComponentType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bans_list" => Ok(Self::BansList),
+            "notes_list" => Ok(Self::NotesList),
+            "rolebans_list" => Ok(Self::RoleBansList),
+            "bans_search" => Ok(Self::BansSearch),
+            "connections_list" => Ok(Self::ConnectionsList),
+            "confirm" => Ok(Self::Confirm),
+            "cancel" => Ok(Self::Cancel),
+            _ => Err(())
+        }
+    }
+}
+
+pub enum DiscordModalType {
+    Ban,
+}
+
+// assume str as custom_id prefix
+impl FromStr for DiscordModalType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ban_modal" => Ok(Self::Ban),
+            _ => Err(())
+        }
+    }
+}
@@ -1,14 +1,19 @@
+use chrono::{DateTime, Duration, Utc};
 use log::error;
 use serenity::all::{
-    CommandOptionType, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, ResolvedOption, ResolvedValue
+    CommandInteraction, CommandOptionType, CreateActionRow, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, Http, ResolvedOption, ResolvedValue, UserId
 };
+use uuid::Uuid;
 
 use crate::{
     bot::{
+        audit::{self, AuditEntry},
         create_response_with_content,
-        utilities::{generate_random_colour, get_user_id_by_login, resolve_user_name},
+        pagination::{page_buttons, pages_count, PER_PAGE},
+        utilities::{generate_random_colour, get_user_id_by_login, prepare_clients, resolve_admin, resolve_user_name, shorten_message},
     },
-    database::{AdminNote, AdminNoteShort, PgDatabase},
+    config::Config,
+    database::{AdminNote, AdminNoteEdit, AdminNoteShort, NewAdminNote, PgDatabase},
 };
 
 static SHORT_MSG_LEN_SYMBOLS: usize = 50;
@@ -20,6 +25,7 @@ pub fn get_registration() -> CreateCommand {
             CreateCommandOption::new(CommandOptionType::SubCommand, "list", "Lists all notes of this user")
                 .add_sub_option(
                     CreateCommandOption::new(CommandOptionType::String, "login", "In-game login")
+                        .set_autocomplete(true)
                         .required(true),
                 ),
         )
@@ -30,9 +36,69 @@ pub fn get_registration() -> CreateCommand {
                         .required(true),
                 ),
         )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Adds a note to this user")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "login", "In-game login")
+                        .set_autocomplete(true)
+                        .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "message", "Note message")
+                        .required(true),
+                )
+                .add_sub_option(severity_option())
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Boolean, "secret", "Hide the note from the player")
+                        .required(false),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Integer, "expires_in", "Minutes until the note expires")
+                        .min_int_value(1)
+                        .required(false),
+                ),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "edit", "Edits a specific note by ID")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Integer, "id", "ID of the note from the 'list' subcommand")
+                        .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "message", "New note message")
+                        .required(false),
+                )
+                .add_sub_option(severity_option())
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Boolean, "secret", "Hide the note from the player")
+                        .required(false),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Integer, "expires_in", "Minutes until the note expires, 0 to never expire")
+                        .min_int_value(0)
+                        .required(false),
+                ),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "delete", "Deletes a specific note by ID")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Integer, "id", "ID of the note from the 'list' subcommand")
+                        .required(true),
+                ),
+        )
 }
 
-pub fn get_options(options: &[ResolvedOption]) -> Result<NotesSubcommand, String> {
+fn severity_option() -> CreateCommandOption {
+    // values match NoteSeverity enum of the game
+    CreateCommandOption::new(CommandOptionType::Integer, "severity", "Note severity")
+        .add_int_choice("None", 0)
+        .add_int_choice("Minor", 1)
+        .add_int_choice("Medium", 2)
+        .add_int_choice("High", 3)
+        .required(false)
+}
+
+pub fn get_options(options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<NotesSubcommand, String> {
     if options.len() != 1 {
         return Err("Invalid options count".to_string());
     }
@@ -42,6 +108,9 @@ pub fn get_options(options: &[ResolvedOption]) -> Result<NotesSubcommand, String
     match subcommand.name {
         "list" => parse_list_subcommand(subcommand),
         "note" => parse_note_subcommand(subcommand),
+        "add" => parse_add_subcommand(subcommand, cmd),
+        "edit" => parse_edit_subcommand(subcommand, cmd),
+        "delete" => parse_delete_subcommand(subcommand, cmd),
         _ => Err("Invalid subcommand type".to_string()),
     }
 }
@@ -70,10 +139,219 @@ fn parse_list_subcommand(opt: &ResolvedOption) -> Result<NotesSubcommand, String
     Err("Invalid or missing 'login' option".to_string())
 }
 
-pub async fn execute(command: NotesSubcommand, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+fn parse_add_subcommand(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<NotesSubcommand, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        let mut login: Option<String> = None;
+        let mut message: Option<String> = None;
+        let mut severity: Option<i32> = None;
+        let mut secret: Option<bool> = None;
+        let mut expires_in: Option<i64> = None;
+
+        for option in suboptions {
+            match (option.name, &option.value) {
+                ("login", ResolvedValue::String(l)) => login = Some(l.to_string()),
+                ("message", ResolvedValue::String(m)) => message = Some(m.to_string()),
+                ("severity", ResolvedValue::Integer(s)) => severity = Some(*s as i32),
+                ("secret", ResolvedValue::Boolean(s)) => secret = Some(*s),
+                ("expires_in", ResolvedValue::Integer(e)) => expires_in = Some(*e),
+                _ => return Err("Invalid options passed".to_string())
+            }
+        }
+
+        let (login, message) = match (login, message) {
+            (Some(login), Some(message)) => (login, message),
+            _ => return Err("Invalid or missing 'login' or 'message' option".to_string()),
+        };
+
+        return Ok(NotesSubcommand::Add {
+            caller_discord_id: cmd.user.id,
+            login,
+            message,
+            severity: severity.unwrap_or(1),
+            secret: secret.unwrap_or(false),
+            expires_in,
+        });
+    }
+    Err("Invalid options provided.".to_string())
+}
+
+fn parse_edit_subcommand(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<NotesSubcommand, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        let mut id: Option<i64> = None;
+        let mut edit = AdminNoteEdit::default();
+
+        for option in suboptions {
+            match (option.name, &option.value) {
+                ("id", ResolvedValue::Integer(i)) => id = Some(*i),
+                ("message", ResolvedValue::String(m)) => edit.message = Some(m.to_string()),
+                ("severity", ResolvedValue::Integer(s)) => edit.severity = Some(*s as i32),
+                ("secret", ResolvedValue::Boolean(s)) => edit.secret = Some(*s),
+                ("expires_in", ResolvedValue::Integer(e)) => edit.expiration_time = Some(expiration_from_minutes(*e)),
+                _ => return Err("Invalid options passed".to_string())
+            }
+        }
+
+        let id = match id {
+            Some(id) => id,
+            None => return Err("Invalid or missing 'id' option".to_string()),
+        };
+
+        if edit.message.is_none() && edit.severity.is_none() && edit.secret.is_none() && edit.expiration_time.is_none() {
+            return Err("Nothing to edit, specify at least one field.".to_string());
+        }
+
+        return Ok(NotesSubcommand::Edit { caller_discord_id: cmd.user.id, id, edit });
+    }
+    Err("Invalid options provided.".to_string())
+}
+
+fn parse_delete_subcommand(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<NotesSubcommand, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        if let Some(ResolvedOption {
+            value: ResolvedValue::Integer(id), ..
+        }) = suboptions.first()
+        {
+            return Ok(NotesSubcommand::Delete { caller_discord_id: cmd.user.id, id: *id });
+        }
+    }
+    Err("Invalid or missing 'id' option".to_string())
+}
+
+// 0 or less means note never expires
+fn expiration_from_minutes(minutes: i64) -> Option<DateTime<Utc>> {
+    if minutes <= 0 {
+        None
+    } else {
+        Some(Utc::now() + Duration::minutes(minutes))
+    }
+}
+
+pub async fn execute(command: NotesSubcommand, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
     match command {
         NotesSubcommand::Note { id } => execute_note_by_id(id as i32, db).await,
         NotesSubcommand::List { login } => execute_list_by_login(login, db).await,
+        NotesSubcommand::Add { .. } => execute_add_note(command, db, config, http).await,
+        NotesSubcommand::Edit { .. } => execute_edit_note(command, db, config, http).await,
+        NotesSubcommand::Delete { .. } => execute_delete_note(command, db, config, http).await,
+    }
+}
+
+async fn execute_add_note(command: NotesSubcommand, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
+    let (caller, login, message, severity, secret, expires_in) = match command {
+        NotesSubcommand::Add { caller_discord_id, login, message, severity, secret, expires_in } => 
+            (caller_discord_id, login, message, severity, secret, expires_in),
+        _ => panic!("Invalid subcommand passed.")
+    };
+
+    let (_, discord_api) = match prepare_clients(config) {
+        Ok(clients) => clients,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    let player_uuid = match get_user_id_by_login(&login, db).await {
+        Some(id) => id,
+        None => return create_response_with_content("No such player found.", true),
+    };
+
+    let audit = AuditEntry::new(caller, "notes.add", &login)
+        .admin(admin_uuid, &admin_name)
+        .param("severity", severity)
+        .param("secret", secret)
+        .param("message", &message);
+
+    let note = NewAdminNote {
+        player_user_id: player_uuid,
+        message,
+        severity,
+        secret,
+        expiration_time: expires_in.and_then(expiration_from_minutes),
+    };
+
+    match db.add_admin_note(note, &admin_uuid).await {
+        Ok(id) => {
+            audit::record(audit.param("note_id", id), http, db, config).await;
+            create_response_with_content(&format!("Successfully added note `{}` to `{}`.", id, login), true)
+        },
+        Err(e) => {
+            error!("Error adding note for {}: {}", login, e);
+            create_response_with_content("Failed to add note.", true)
+        }
+    }
+}
+
+async fn execute_edit_note(command: NotesSubcommand, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
+    let (caller, id, edit) = match command {
+        NotesSubcommand::Edit { caller_discord_id, id, edit } => (caller_discord_id, id, edit),
+        _ => panic!("Invalid subcommand passed.")
+    };
+
+    let (_, discord_api) = match prepare_clients(config) {
+        Ok(clients) => clients,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    let mut audit = AuditEntry::new(caller, "notes.edit", format!("note {}", id))
+        .admin(admin_uuid, &admin_name);
+    if let Some(message) = &edit.message {
+        audit = audit.param("message", message);
+    }
+    if let Some(severity) = edit.severity {
+        audit = audit.param("severity", severity);
+    }
+    if let Some(secret) = edit.secret {
+        audit = audit.param("secret", secret);
+    }
+    if let Some(expiration_time) = edit.expiration_time {
+        audit = audit.param("expiration_time", expiration_time.map(|t| t.to_string()).unwrap_or("Never".to_string()));
+    }
+
+    match db.edit_admin_note(id as i32, edit, &admin_uuid).await {
+        Ok(0) => create_response_with_content(&format!("Note with ID `{}` not found.", id), true),
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            create_response_with_content(&format!("Successfully edited note `{}`.", id), true)
+        },
+        Err(e) => {
+            error!("Error editing note with ID {}: {}", id, e);
+            create_response_with_content("Failed to edit note.", true)
+        }
+    }
+}
+
+async fn execute_delete_note(command: NotesSubcommand, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
+    let (caller, id) = match command {
+        NotesSubcommand::Delete { caller_discord_id, id } => (caller_discord_id, id),
+        _ => panic!("Invalid subcommand passed.")
+    };
+
+    let (_, discord_api) = match prepare_clients(config) {
+        Ok(clients) => clients,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    match db.delete_admin_note(id as i32, &admin_uuid).await {
+        Ok(0) => create_response_with_content(&format!("Note with ID `{}` not found.", id), true),
+        Ok(_) => {
+            let audit = AuditEntry::new(caller, "notes.delete", format!("note {}", id)).admin(admin_uuid, &admin_name);
+            audit::record(audit, http, db, config).await;
+            create_response_with_content(&format!("Successfully deleted note `{}`.", id), true)
+        },
+        Err(e) => {
+            error!("Error deleting note with ID {}: {}", id, e);
+            create_response_with_content("Failed to delete note.", true)
+        }
     }
 }
 
@@ -83,27 +361,41 @@ async fn execute_list_by_login(login: String, db: &PgDatabase) -> CreateInteract
         None => return create_response_with_content("No such player found.", true),
     };
 
-    match db.get_notes_list(&uuid).await {
-        Ok(notes) => {
-            let description = notes
-                .iter()
-                .map(format_short_note_summary)
-                .collect::<Vec<String>>()
-                .join("\n");
+    match list_page(&uuid, &login, 0, db).await {
+        Ok((embed, components)) => CreateInteractionResponseFollowup::new().add_embed(embed).components(components).ephemeral(true),
+        Err(e) => create_response_with_content(&e, true),
+    }
+}
 
-            let embed = CreateEmbed::new()
-                .title(format!("Notes for `{}`", login))
-                .description(description)
-                .color(generate_random_colour())
-                .footer(CreateEmbedFooter::new("VoidRelay by JerryImMouse"));
+pub static LIST_PAGE_PREFIX: &str = "notes_list";
 
-            CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
-        }
+/// Builds a single page of player's notes list with navigation buttons.
+pub async fn list_page(uuid: &Uuid, login: &str, page: i64, db: &PgDatabase) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let (notes, total) = match tokio::try_join!(db.get_notes_page(uuid, page, PER_PAGE), db.count_notes(uuid)) {
+        Ok(result) => result,
         Err(err) => {
             error!("Error retrieving notes for {}: {}", login, err);
-            create_response_with_content("Failed to retrieve notes.", true)
+            return Err("Failed to retrieve notes.".to_string());
         }
+    };
+
+    let pages = pages_count(total);
+    let mut description = notes
+        .iter()
+        .map(format_short_note_summary)
+        .collect::<Vec<String>>()
+        .join("\n");
+    if description.is_empty() {
+        description = "Nothing found.".to_string();
     }
+
+    let embed = CreateEmbed::new()
+        .title(format!("Notes for `{}`", login))
+        .description(description)
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Page {}/{} • VoidRelay by JerryImMouse", page + 1, pages)));
+
+    Ok((embed, page_buttons(LIST_PAGE_PREFIX, &uuid.to_string(), page, pages)))
 }
 
 async fn execute_note_by_id(id: i32, db: &PgDatabase) -> CreateInteractionResponseFollowup {
@@ -132,19 +424,7 @@ async fn execute_note_by_id(id: i32, db: &PgDatabase) -> CreateInteractionRespon
 }
 
 fn format_short_note_summary(note: &AdminNoteShort) -> String {
-    let short_msg = if note.message.chars().count() <= SHORT_MSG_LEN_SYMBOLS {
-        note.message.clone()
-    } else {
-        let end_index = note
-            .message
-            .char_indices()
-            .nth(SHORT_MSG_LEN_SYMBOLS)
-            .map(|(idx, _)| idx)
-            .unwrap_or_else(|| note.message.len());
-        format!("{}...", &note.message[..end_index])
-    };
-
-    format!("**{}**. {}", note.admin_notes_id, short_msg)
+    format!("**{}**. {}", note.admin_notes_id, shorten_message(&note.message, SHORT_MSG_LEN_SYMBOLS))
 }
 
 fn format_admin_note(note: &AdminNote, created_by: &str, last_edited_by: &str) -> String {
@@ -156,7 +436,7 @@ fn format_admin_note(note: &AdminNote, created_by: &str, last_edited_by: &str) -
 🕒 **Last Edited At:** {}
 🗑️ **Deleted:** {}
 "#,
-        note.round_id,
+        note.round_id.map(|id| id.to_string()).unwrap_or("no round".to_string()),
         created_by,
         note.created_at,
         last_edited_by,
@@ -187,4 +467,21 @@ fn format_admin_note(note: &AdminNote, created_by: &str, last_edited_by: &str) -
 pub enum NotesSubcommand {
     List { login: String },
     Note { id: i64 },
+    Add {
+        caller_discord_id: UserId,
+        login: String,
+        message: String,
+        severity: i32,
+        secret: bool,
+        expires_in: Option<i64>,
+    },
+    Edit {
+        caller_discord_id: UserId,
+        id: i64,
+        edit: AdminNoteEdit,
+    },
+    Delete {
+        caller_discord_id: UserId,
+        id: i64,
+    },
 }
@@ -0,0 +1,123 @@
+use log::error;
+use serenity::all::{ButtonStyle, CommandOptionType, CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, ResolvedOption, ResolvedValue};
+
+use crate::{bot::{commands::{ban, notes}, create_response_with_content, pagination::page_id, utilities::{format_hwid, generate_random_colour, get_user_id_by_login}}, database::{PgDatabase, PlayerProfile}};
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("player")
+        .description("Player profiles")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "info",
+                "Shows everything known about the player",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "login",
+                    "In-Game login",
+                )
+                .set_autocomplete(true)
+                .required(true),
+            ),
+        )
+}
+
+pub fn get_options(options: &[ResolvedOption]) -> Result<PlayerSubcommand, String> {
+    if options.len() != 1 {
+        return Err("Invalid options count".to_string());
+    }
+
+    let subcommand = options.first().unwrap();
+    match (subcommand.name, &subcommand.value) {
+        ("info", ResolvedValue::SubCommand(suboptions)) => match suboptions.first() {
+            Some(ResolvedOption { value: ResolvedValue::String(login), .. }) => Ok(PlayerSubcommand::Info(login.to_string())),
+            _ => Err("Invalid or missing 'login' option".to_string()),
+        },
+        _ => Err("Invalid subcommand.".to_string())
+    }
+}
+
+pub async fn execute(cmd: PlayerSubcommand, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+    match cmd {
+        PlayerSubcommand::Info(login) => execute_info_cmd(login, db).await,
+    }
+}
+
+async fn execute_info_cmd(login: String, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+    let uuid = match get_user_id_by_login(&login, db).await {
+        Some(uuid) => uuid,
+        None => return create_response_with_content("No such player found.", true),
+    };
+
+    let profile = match db.get_player_profile(&uuid).await {
+        Ok(Some(profile)) => profile,
+        Ok(None) => return create_response_with_content("No such player found.", true),
+        Err(e) => {
+            error!("Error retrieving profile of {}: {}", login, e);
+            return create_response_with_content("Failed to retrieve player profile.", true);
+        }
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("Player `{}`", profile.last_seen_user_name))
+        .description(format_profile(&profile))
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+    let key = uuid.to_string();
+    let buttons = vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(page_id(ban::LIST_PAGE_PREFIX, 0, &key))
+            .label(format!("Bans ({})", profile.bans))
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(page_id(notes::LIST_PAGE_PREFIX, 0, &key))
+            .label(format!("Notes ({})", profile.notes))
+            .style(ButtonStyle::Secondary),
+    ])];
+
+    CreateInteractionResponseFollowup::new().add_embed(embed).components(buttons).ephemeral(true)
+}
+
+fn format_profile(profile: &PlayerProfile) -> String {
+    let mut formatted = format!(
+        r#"🆔 **UUID:** {}
+📅 **First Seen:** {}
+🕒 **Last Seen:** {}
+📍 **Last Address:** {}
+"#,
+        profile.user_id,
+        profile.first_seen_time,
+        profile.last_seen_time,
+        profile.last_seen_address,
+    );
+
+    if let Some(hwid) = profile.last_seen_hwid.as_ref().filter(|hwid| !hwid.is_empty()) {
+        formatted.push_str(&format!("🖥️ **Last HWID:** {}\n", format_hwid(hwid)));
+    }
+
+    formatted.push_str(&format!(
+        r#"⏱️ **Playtime:** {}h {}m
+🔒 **Bans:** {}
+🎭 **Role Bans:** {}
+📝 **Notes:** {}
+"#,
+        profile.playtime_seconds / 3600,
+        profile.playtime_seconds % 3600 / 60,
+        profile.bans,
+        profile.role_bans,
+        profile.notes,
+    ));
+
+    formatted.push_str(if profile.whitelisted {
+        "📜 **Whitelisted:** Yes\n"
+    } else {
+        "📜 **Whitelisted:** No\n"
+    });
+
+    formatted
+}
+
+pub enum PlayerSubcommand {
+    Info(String),
+}
@@ -0,0 +1,537 @@
+use chrono::{Duration, Utc};
+use log::{error, info};
+use serenity::all::{CommandInteraction, CommandOptionType, CreateActionRow, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, Http, ResolvedOption, ResolvedValue, UserId};
+use uuid::Uuid;
+
+use crate::{api::ss14client::{PardonRequest, RoleBanRequest, SS14ApiActor, SS14ApiClient}, bot::{audit::{self, AuditEntry}, confirmation::{Confirmations, PendingAction}, create_response_with_content, pagination::{page_buttons, pages_count, PER_PAGE}, permissions::check_duration_limit, utilities::{format_duration, format_network, generate_random_colour, get_user_id_by_login, parse_duration, prepare_clients, resolve_admin, resolve_optional_user_name, resolve_user_name, shorten_message}}, config::Config, database::{NewServerRoleBan, PgDatabase, ServerRoleBan, ServerRoleBanShort}, error::Error};
+
+static SHORT_MSG_LEN_SYMBOLS: usize = 50;
+static JOB_PREFIX: &str = "Job:";
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("rolebans")
+        .description("Manages role (job) bans at SS14 server")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "list",
+                "Lists all role bans for specified player",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "login",
+                    "In-Game login",
+                )
+                .set_autocomplete(true)
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "info",
+                "Info about specific role ban by ID",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "id",
+                    "Role ban ID",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "pardon",
+                "Pardon specific role ban",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "id",
+                    "Role ban ID",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "ban",
+                "Bans specific player from a role",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "login",
+                    "In-Game Login",
+                )
+                .set_autocomplete(true)
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "role",
+                    "Role to ban from, e.g. `Captain` or `Job:Captain`",
+                )
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "duration",
+                    "Ban duration, e.g. `30m`, `3d`, `2w`, `1mo` or `perm`",
+                )
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "reason",
+                    "Reason to ban for",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "severity",
+                    "Severity to ban for",
+                )
+                .required(false),
+            ),
+        )
+}
+
+fn parse_id_option(opt: &ResolvedOption) -> Result<i64, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        if let Some(ResolvedOption {
+            value: ResolvedValue::Integer(id), ..
+        }) = suboptions.first()
+        {
+            return Ok(*id);
+        }
+    }
+    Err("Invalid or missing 'id' option".to_string())
+}
+
+fn parse_list_options(opt: &ResolvedOption) -> Result<RoleBansSubcommand, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        if let Some(ResolvedOption {
+            value: ResolvedValue::String(login), ..
+        }) = suboptions.first()
+        {
+            return Ok(RoleBansSubcommand::List(login.to_string()));
+        }
+    }
+    Err("Invalid or missing 'login' option".to_string())
+}
+
+fn parse_ban_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<RoleBansSubcommand, String> {
+    if let ResolvedValue::SubCommand(suboptions) = &opt.value {
+        let mut login: Option<String> = None;
+        let mut role: Option<String> = None;
+        let mut minutes: Option<i64> = None;
+        let mut reason: Option<String> = None;
+        let mut severity: Option<u16> = None;
+
+        for option in suboptions {
+            match (option.name, &option.value) {
+                ("login", ResolvedValue::String(l)) => login = Some(l.to_string()),
+                ("role", ResolvedValue::String(r)) => role = Some(normalize_role_id(r)),
+                ("duration", ResolvedValue::String(d)) => minutes = Some(parse_duration(d)?),
+                ("reason", ResolvedValue::String(r)) => reason = Some(r.to_string()),
+                ("severity", ResolvedValue::Integer(s)) => severity = Some(*s as u16),
+                _ => return Err("Invalid options passed".to_string())
+            }
+        }
+
+        let (login, role) = match (login, role) {
+            (Some(login), Some(role)) => (login, role),
+            _ => return Err("Banning player and role couldn't be none".to_string()),
+        };
+        let minutes = match minutes {
+            Some(minutes) => minutes,
+            None => return Err("Ban duration is required, use `perm` for permanent bans.".to_string()),
+        };
+
+        return Ok(RoleBansSubcommand::Ban {
+            caller_discord_id: cmd.user.id,
+            banning_player_login: login,
+            role,
+            minutes,
+            reason: reason.unwrap_or("No reason provided".to_string()),
+            severity: severity.unwrap_or(2),
+        });
+    }
+    Err("Invalid options provided.".to_string())
+}
+
+// the game stores job bans as `Job:<JobId>`
+fn normalize_role_id(role: &str) -> String {
+    if role.contains(':') {
+        role.to_string()
+    } else {
+        format!("{}{}", JOB_PREFIX, role)
+    }
+}
+
+pub fn get_options(options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<RoleBansSubcommand, String> {
+    if options.len() != 1 {
+        return Err("Invalid options length".to_string());
+    }
+
+    let subcommand = options.first().unwrap();
+    match subcommand.name {
+        "list" => parse_list_options(subcommand),
+        "info" => parse_id_option(subcommand).map(|id| RoleBansSubcommand::Info(id as i32)),
+        "pardon" => parse_id_option(subcommand).map(|id| RoleBansSubcommand::Pardon { caller_discord_id: cmd.user.id, id }),
+        "ban" => parse_ban_options(subcommand, cmd),
+        _ => Err("Invalid subcommand".to_string())
+    }
+}
+
+pub async fn execute(cmd: RoleBansSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations) -> CreateInteractionResponseFollowup {
+    match cmd {
+        RoleBansSubcommand::Ban { .. } => execute_ban_cmd(cmd, db, config, confirmations).await,
+        RoleBansSubcommand::List(login) => execute_list_cmd(login, db).await,
+        RoleBansSubcommand::Pardon { .. } => execute_pardon_cmd(cmd, db, config, confirmations).await,
+        RoleBansSubcommand::Info(id) => execute_info_cmd(id, db).await,
+    }
+}
+
+async fn execute_list_cmd(login: String, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+    let uuid = match get_user_id_by_login(&login, db).await {
+        Some(id) => id,
+        None => return create_response_with_content("No such player found.", true),
+    };
+
+    match list_page(&uuid, &login, 0, db).await {
+        Ok((embed, components)) => CreateInteractionResponseFollowup::new().add_embed(embed).components(components).ephemeral(true),
+        Err(e) => create_response_with_content(&e, true),
+    }
+}
+
+pub static LIST_PAGE_PREFIX: &str = "rolebans_list";
+
+pub async fn list_page(uuid: &Uuid, login: &str, page: i64, db: &PgDatabase) -> Result<(CreateEmbed, Vec<CreateActionRow>), String> {
+    let (bans, total) = match tokio::try_join!(db.get_role_bans_page(uuid, page, PER_PAGE), db.count_role_bans(uuid)) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Error retrieving role bans for {}. Error {}", login, e);
+            return Err("Failed to retrieve role bans.".to_string());
+        }
+    };
+
+    let pages = pages_count(total);
+    let mut description = bans
+        .iter()
+        .map(format_short_role_ban_summary)
+        .collect::<Vec<String>>()
+        .join("\n");
+    if description.is_empty() {
+        description = "Nothing found.".to_string();
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!("Role bans for `{}`", login))
+        .description(description)
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Page {}/{} • VoidRelay By JerryImMouse", page + 1, pages)));
+
+    Ok((embed, page_buttons(LIST_PAGE_PREFIX, &uuid.to_string(), page, pages)))
+}
+
+async fn execute_info_cmd(id: i32, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+    match db.get_role_ban_by_id(id).await {
+        Ok(Some(ban)) => {
+            let created_by = match ban.banning_admin {
+                Some(admin) => resolve_user_name(db, &admin).await,
+                None => "System".to_string(),
+            };
+            let unbanned_by = if let Some(admin) = ban.unbanning_admin { Some(resolve_user_name(db, &admin).await) } else { None };
+            let player = match ban.player_user_id {
+                Some(player) => resolve_user_name(db, &player).await,
+                None => "Unknown".to_string(),
+            };
+
+            let fmt = format_role_ban_summary(&ban, &created_by, unbanned_by);
+            let embed = CreateEmbed::new()
+                .title(format!("Role ban of `{}`", player))
+                .description(fmt)
+                .colour(generate_random_colour())
+                .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+            CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
+        },
+        Ok(None) => {
+            create_response_with_content(&format!("Role ban with id: {} is not found", id), true)
+        }
+        Err(e) => {
+            error!("Error retrieving role ban by id: {}. Error: {}", id, e);
+            create_response_with_content("Error happened retrieving role ban.", true)
+        }
+    }
+}
+
+async fn execute_pardon_cmd(cmd: RoleBansSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations) -> CreateInteractionResponseFollowup {
+    let (caller, id) = match cmd {
+        RoleBansSubcommand::Pardon { caller_discord_id, id } => (caller_discord_id, id),
+        _ => panic!("Invalid subcommand passed")
+    };
+
+    let clients = prepare_clients(config);
+    if let Err(e) = clients {
+        return create_response_with_content(&e, true);
+    }
+    let (_, discord_api) = clients.unwrap();
+
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    let ban = match db.get_role_ban_by_id(id as i32).await {
+        Ok(Some(ban)) => ban,
+        Ok(None) => return create_response_with_content(&format!("Role ban with id: {} is not found", id), true),
+        Err(e) => {
+            error!("Error retrieving role ban by id: {}. Error: {}", id, e);
+            return create_response_with_content("Error happened retrieving role ban.", true);
+        }
+    };
+    if ban.unban_time.is_some() {
+        return create_response_with_content(&format!("Role ban with id: {} is already pardoned", id), true);
+    }
+    let player = resolve_optional_user_name(db, ban.player_user_id.as_ref()).await;
+
+    let embed = CreateEmbed::new()
+        .title(format!("Pardon role ban `{}` of `{}`?", id, player))
+        .description(format!(
+            "👤 **Player:** {}\n🎭 **Role:** {}\n📅 **Ban Time:** {}\n\n📝 **Reason:**\n{}",
+            player, ban.role_id, ban.ban_time, ban.reason
+        ))
+        .colour(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Expires in {} seconds • VoidRelay By JerryImMouse", confirmations.timeout().as_secs())));
+
+    let audit = AuditEntry::new(caller, "rolebans.pardon", player)
+        .admin(admin_uuid, &admin_name)
+        .param("role_ban_id", id)
+        .param("role", &ban.role_id);
+
+    let request = PardonRequest::new(id as i32, SS14ApiActor::new(admin_uuid, &admin_name));
+    let buttons = confirmations.add(caller, PendingAction::RolePardon { id: id as i32, admin: admin_uuid, request, audit });
+    CreateInteractionResponseFollowup::new().add_embed(embed).components(buttons).ephemeral(true)
+}
+
+/// Sends role pardon confirmed by admin to the game server, or writes it to database if the server can't.
+pub async fn execute_confirmed_pardon(id: i32, admin: Uuid, request: PardonRequest, audit: AuditEntry, db: &PgDatabase, config: &Config, http: &Http) -> String {
+    let ss14_api = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return "Unable to setup ss14 client.".to_string();
+        }
+    };
+
+    let result = match ss14_api.role_pardon(request).await {
+        Err(Error::SS14ApiUnsupported(_)) => {
+            info!("SS14 api doesn't support role pardons, writing role unban {} to database.", id);
+            match db.add_role_unban(id, &admin).await {
+                Ok(0) => return format!("Role ban with id: {} is not found or already pardoned", id),
+                Ok(_) => Ok(()),
+                Err(e) => Err(e),
+            }
+        },
+        other => other,
+    };
+
+    match result {
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            format!("Successfully pardoned role ban with id: {}", id)
+        },
+        Err(e) => {
+            error!("Unable to pardon role ban. Id: {id}. Error: {e}");
+            if let Error::SS14ApiError(e) = e {
+                format!("Error during role pardon: {}", e)
+            } else {
+                "Error occured during role ban pardon.".to_string()
+            }
+        }
+    }
+}
+
+async fn execute_ban_cmd(cmd: RoleBansSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations) -> CreateInteractionResponseFollowup {
+    let (caller, player_login, role, minutes, reason, severity) = match cmd {
+        RoleBansSubcommand::Ban {
+            caller_discord_id,
+            banning_player_login,
+            role,
+            minutes,
+            reason,
+            severity
+        } => (caller_discord_id, banning_player_login, role, minutes, reason, severity),
+        _ => panic!("Invalid subcommand passed")
+    };
+
+    let clients = prepare_clients(config);
+    if let Err(e) = clients {
+        return create_response_with_content(&e, true);
+    }
+    let (_, discord_api) = clients.unwrap();
+
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+
+    let banning_uuid = match get_user_id_by_login(&player_login, db).await {
+        Some(uuid) => uuid,
+        None => {
+            error!("Unable to find uuid of user: {}", player_login);
+            return create_response_with_content("Unable to find banning player.", true);
+        }
+    };
+
+    if let Err(e) = check_duration_limit(&admin_uuid, minutes, db, config).await {
+        return create_response_with_content(&e, true);
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!("Ban `{}` from `{}`?", player_login, role))
+        .description(format!(
+            "👤 **Player:** {}\n🆔 **UUID:** {}\n🎭 **Role:** {}\n⏳ **Duration:** {}\n⚠️ **Severity:** {}\n\n📝 **Reason:**\n{}",
+            player_login, banning_uuid, role, format_duration(minutes), severity, reason
+        ))
+        .colour(generate_random_colour())
+        .footer(CreateEmbedFooter::new(format!("Expires in {} seconds • VoidRelay By JerryImMouse", confirmations.timeout().as_secs())));
+
+    let audit = AuditEntry::new(caller, "rolebans.ban", &player_login)
+        .admin(admin_uuid, &admin_name)
+        .param("role", &role)
+        .param("duration", format_duration(minutes))
+        .param("severity", severity)
+        .param("reason", &reason);
+
+    // written to database as is when SS14 api can't role ban
+    let ban = NewServerRoleBan {
+        player_user_id: banning_uuid,
+        banning_admin: admin_uuid,
+        role_id: role.clone(),
+        reason: reason.clone(),
+        severity: severity as i32,
+        expiration_time: if minutes > 0 { Some(Utc::now() + Duration::minutes(minutes)) } else { None },
+    };
+
+    let actor = SS14ApiActor::new(admin_uuid, &admin_name);
+    let request = RoleBanRequest::new(player_login.to_owned(), banning_uuid, role, reason, minutes as i32, severity, actor);
+
+    let buttons = confirmations.add(caller, PendingAction::RoleBan { login: player_login, request, ban, audit });
+    CreateInteractionResponseFollowup::new().add_embed(embed).components(buttons).ephemeral(true)
+}
+
+/// Sends role ban confirmed by admin to the game server, or writes it to database if the server can't.
+pub async fn execute_confirmed_ban(player_login: String, request: RoleBanRequest, ban: NewServerRoleBan, audit: AuditEntry, db: &PgDatabase, config: &Config, http: &Http) -> String {
+    let ss14_api = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return "Unable to setup ss14 client.".to_string();
+        }
+    };
+
+    let role = ban.role_id.clone();
+    let result = match ss14_api.role_ban(request).await {
+        Err(Error::SS14ApiUnsupported(_)) => {
+            info!("SS14 api doesn't support role bans, writing role ban of {} to database.", player_login);
+            db.add_role_ban(ban).await.map(|_| ())
+        },
+        other => other,
+    };
+
+    match result {
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            format!("Successfully banned player `{}` from `{}`", player_login, role)
+        },
+        Err(e) => {
+            error!("Unable to role ban player: {player_login}. Error: {e}");
+            if let Error::SS14ApiError(e) = e {
+                format!("Error during role ban: {}", e)
+            } else {
+                "Error occured during role ban.".to_string()
+            }
+        }
+    }
+}
+
+fn format_short_role_ban_summary(ban: &ServerRoleBanShort) -> String {
+    format!("**{}**. `{}` {}", ban.server_role_ban_id, ban.role_id, shorten_message(&ban.reason, SHORT_MSG_LEN_SYMBOLS))
+}
+
+fn format_role_ban_summary(ban: &ServerRoleBan, created_by: &str, unbanned_by: Option<String>) -> String {
+    let mut formatted = format!(
+        r#"🔒 **Role Ban ID:** {}
+🎭 **Role:** {}
+📅 **Ban Time:** {}
+✍️ **Banning Admin:** {}
+⚠️ **Severity:** {}
+"#,
+        ban.server_role_ban_id,
+        ban.role_id,
+        ban.ban_time,
+        created_by,
+        ban.severity,
+    );
+
+    if let Some(address) = &ban.address {
+        formatted.push_str(&format!("📍 **Address:** {}\n", format_network(address)));
+    }
+
+    if let Some(expiration_time) = ban.expiration_time {
+        formatted.push_str(&format!("⏳ **Expiration Time:** {}\n", expiration_time));
+    } else {
+        formatted.push_str("⏳ **Expiration Time:** Never\n");
+    }
+
+    if let Some(last_edited_at) = ban.last_edited_at {
+        formatted.push_str(&format!("🕒 **Last Edited At:** {}\n", last_edited_at));
+    }
+
+    if let Some(round_id) = ban.round_id {
+        formatted.push_str(&format!("✨ **Round ID:** {}\n", round_id));
+    }
+
+    if let Some(unban_time) = ban.unban_time {
+        formatted.push_str(&format!("🔓 **Pardoned At:** {}\n", unban_time));
+    }
+
+    if let Some(unbanned_by) = unbanned_by {
+        formatted.push_str(&format!("🕊️ **Pardoned By:** {}\n", unbanned_by));
+    }
+
+    formatted.push_str(&format!("\n📝 **Reason:**\n{}", ban.reason));
+
+    formatted
+}
+
+pub enum RoleBansSubcommand {
+    List(String),
+    Info(i32),
+    Pardon {
+        caller_discord_id: UserId,
+        id: i64
+    },
+    Ban {
+        caller_discord_id: UserId,
+        banning_player_login: String,
+        role: String,
+        minutes: i64,
+        reason: String,
+        severity: u16,
+    }
+}
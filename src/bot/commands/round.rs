@@ -0,0 +1,198 @@
+use log::error;
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, Http, ResolvedOption, ResolvedValue, UserId};
+
+use crate::{api::ss14client::{ForcePresetRequest, RoundRequest, SS14ApiActor, SS14ApiClient}, bot::{audit::{self, AuditEntry}, confirmation::{Confirmations, PendingAction}, create_response_with_content, utilities::{generate_random_colour, prepare_clients, resolve_admin}}, config::Config, database::PgDatabase, error::Error};
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("round")
+        .description("Controls the round at SS14 server")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "start",
+            "Starts the round from lobby",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "end",
+            "Ends current round",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "restart",
+            "Restarts the round right now",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "preset",
+                "Forces game preset of the next round",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "preset",
+                    "Preset ID, e.g. `Secret` or `Extended`",
+                )
+                .required(true),
+            ),
+        )
+}
+
+pub fn get_options(options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<RoundSubcommand, String> {
+    if options.len() != 1 {
+        return Err("Invalid options count".to_string());
+    }
+
+    let caller_discord_id = cmd.user.id;
+    let subcommand = options.first().unwrap();
+    match (subcommand.name, &subcommand.value) {
+        ("start", _) => Ok(RoundSubcommand::Action { caller_discord_id, action: RoundAction::Start }),
+        ("end", _) => Ok(RoundSubcommand::Action { caller_discord_id, action: RoundAction::End }),
+        ("restart", _) => Ok(RoundSubcommand::Action { caller_discord_id, action: RoundAction::Restart }),
+        ("preset", ResolvedValue::SubCommand(suboptions)) => match suboptions.first() {
+            Some(ResolvedOption { value: ResolvedValue::String(preset), .. }) => Ok(RoundSubcommand::Preset {
+                caller_discord_id,
+                preset: preset.to_string(),
+            }),
+            _ => Err("Invalid or missing 'preset' option".to_string()),
+        },
+        _ => Err("Invalid subcommand.".to_string())
+    }
+}
+
+pub async fn execute(cmd: RoundSubcommand, db: &PgDatabase, config: &Config, confirmations: &Confirmations, http: &Http) -> CreateInteractionResponseFollowup {
+    let caller = match &cmd {
+        RoundSubcommand::Action { caller_discord_id, .. } | RoundSubcommand::Preset { caller_discord_id, .. } => *caller_discord_id,
+    };
+
+    let clients = prepare_clients(config);
+    if let Err(e) = clients {
+        return create_response_with_content(&e, true);
+    }
+    let (ss14_api, discord_api) = clients.unwrap();
+
+    let (admin_uuid, admin_name) = match resolve_admin(caller, &discord_api, db).await {
+        Ok(admin) => admin,
+        Err(e) => return create_response_with_content(&e, true),
+    };
+    let actor = SS14ApiActor::new(admin_uuid, &admin_name);
+
+    match cmd {
+        RoundSubcommand::Preset { preset, .. } => {
+            let audit = AuditEntry::new(caller, "round.preset", &preset).admin(admin_uuid, &admin_name);
+            match ss14_api.force_preset(ForcePresetRequest::new(preset.clone(), actor)).await {
+                Ok(_) => {
+                    audit::record(audit, http, db, config).await;
+                    create_response_with_content(&format!("Next round preset is forced to `{}`", preset), true)
+                },
+                Err(e) => create_response_with_content(&round_error("force preset", e), true),
+            }
+        },
+        RoundSubcommand::Action { action, .. } => {
+            let audit = AuditEntry::new(caller, action.audit_action(), "round").admin(admin_uuid, &admin_name);
+            let request = RoundRequest::new(actor);
+
+            if !action.is_destructive() {
+                return create_response_with_content(&execute_round_action(action, request, &ss14_api, audit, db, config, http).await, true);
+            }
+
+            let embed = CreateEmbed::new()
+                .title(format!("{} the round?", action))
+                .description("Everyone on the server will be affected.")
+                .colour(generate_random_colour())
+                .footer(CreateEmbedFooter::new(format!("Expires in {} seconds • VoidRelay By JerryImMouse", confirmations.timeout().as_secs())));
+
+            let buttons = confirmations.add(caller, PendingAction::Round { action, request, audit });
+            CreateInteractionResponseFollowup::new().add_embed(embed).components(buttons).ephemeral(true)
+        }
+    }
+}
+
+/// Sends round action confirmed by admin to the game server.
+pub async fn execute_confirmed_action(action: RoundAction, request: RoundRequest, audit: AuditEntry, db: &PgDatabase, config: &Config, http: &Http) -> String {
+    let ss14_api = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return "Unable to setup ss14 client.".to_string();
+        }
+    };
+
+    execute_round_action(action, request, &ss14_api, audit, db, config, http).await
+}
+
+async fn execute_round_action(action: RoundAction, request: RoundRequest, ss14_api: &SS14ApiClient, audit: AuditEntry, db: &PgDatabase, config: &Config, http: &Http) -> String {
+    let result = match action {
+        RoundAction::Start => ss14_api.round_start(request).await,
+        RoundAction::End => ss14_api.round_end(request).await,
+        RoundAction::Restart => ss14_api.round_restart(request).await,
+    };
+
+    match result {
+        Ok(_) => {
+            audit::record(audit, http, db, config).await;
+            format!("Successfully {} the round.", action.done())
+        },
+        Err(e) => round_error(&action.to_string().to_lowercase(), e),
+    }
+}
+
+fn round_error(action: &str, e: Error) -> String {
+    error!("Unable to {} the round. Error: {e}", action);
+    if let Error::SS14ApiError(e) = e {
+        format!("Error during round {}: {}", action, e)
+    } else {
+        format!("Error occured during round {}.", action)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RoundAction {
+    Start,
+    End,
+    Restart,
+}
+
+impl RoundAction {
+    // ending or restarting kicks everyone out of the round
+    fn is_destructive(&self) -> bool {
+        matches!(self, Self::End | Self::Restart)
+    }
+
+    fn done(&self) -> &'static str {
+        match self {
+            Self::Start => "started",
+            Self::End => "ended",
+            Self::Restart => "restarted",
+        }
+    }
+
+    fn audit_action(&self) -> &'static str {
+        match self {
+            Self::Start => "round.start",
+            Self::End => "round.end",
+            Self::Restart => "round.restart",
+        }
+    }
+}
+
+impl std::fmt::Display for RoundAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Start => write!(f, "Start"),
+            Self::End => write!(f, "End"),
+            Self::Restart => write!(f, "Restart"),
+        }
+    }
+}
+
+pub enum RoundSubcommand {
+    Action {
+        caller_discord_id: UserId,
+        action: RoundAction,
+    },
+    Preset {
+        caller_discord_id: UserId,
+        preset: String,
+    },
+}
@@ -0,0 +1,64 @@
+use log::error;
+use serenity::all::{CreateCommand, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup};
+
+use crate::{api::ss14client::{SS14ApiClient, ServerStatus}, bot::{create_response_with_content, utilities::generate_random_colour}, config::Config};
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("status")
+        .description("Shows SS14 server status")
+}
+
+pub async fn execute(config: &Config) -> CreateInteractionResponseFollowup {
+    let ss14_client = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client: {e}");
+            return create_response_with_content("Unable to setup ss14 client.", true);
+        }
+    };
+
+    match ss14_client.status(&config.status_url()).await {
+        Ok(status) => {
+            let embed = CreateEmbed::new()
+                .title(&status.name)
+                .description(format_status(&status))
+                .color(generate_random_colour())
+                .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+            CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
+        },
+        Err(e) => {
+            error!("Error fetching server status: {e}");
+            create_response_with_content("Server is unreachable.", true)
+        }
+    }
+}
+
+fn format_status(status: &ServerStatus) -> String {
+    let mut formatted = format!(
+        "👥 **Players:** {}/{}\n🎬 **Run Level:** {}\n",
+        status.players, status.soft_max_players, status.run_level
+    );
+
+    if let Some(round_id) = status.round_id {
+        formatted.push_str(&format!("✨ **Round ID:** {}\n", round_id));
+    }
+
+    if let Some(preset) = &status.preset {
+        formatted.push_str(&format!("🎲 **Preset:** {}\n", preset));
+    }
+
+    if let Some(round_start_time) = status.round_start_time {
+        formatted.push_str(&format!("🕒 **Round Started:** <t:{}:R>\n", round_start_time.timestamp()));
+    }
+
+    formatted
+}
+
+/// Short status line used as bot presence, e.g. `42/80 players — Round 1234`.
+pub fn format_presence(status: &ServerStatus) -> String {
+    match status.round_id {
+        Some(round_id) => format!("{}/{} players — Round {}", status.players, status.soft_max_players, round_id),
+        None => format!("{}/{} players — {}", status.players, status.soft_max_players, status.run_level),
+    }
+}
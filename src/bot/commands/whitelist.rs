@@ -1,43 +1,45 @@
 use log::error;
-use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption, CreateInteractionResponseFollowup, ResolvedOption, ResolvedValue};
+use serenity::all::{CommandInteraction, CommandOptionType, CreateCommand, CreateCommandOption, CreateInteractionResponseFollowup, Http, ResolvedOption, ResolvedValue, UserId};
 
-use crate::{bot::{create_response_with_content, utilities::get_user_id_by_login}, database::PgDatabase, error::Error};
+use crate::{bot::{audit::{self, AuditEntry}, create_response_with_content, utilities::get_user_id_by_login}, config::Config, database::PgDatabase, error::Error};
 
 pub fn get_registration() -> CreateCommand {
     CreateCommand::new("whitelist")
         .description("Add user to whitelist, literally `whitelistadd` in-game")
         .add_option(CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Adds to whitelist")
             .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "login", "In-Game Login")
+                .set_autocomplete(true)
                 .required(true)
         )
     )
         .add_option(CreateCommandOption::new(CommandOptionType::SubCommand, "rm", "Removes from whitelist")
             .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "login", "In-Game Login")
+                .set_autocomplete(true)
                 .required(true)
             )
     )
 }
 
-fn parse_rm_options(opt: &ResolvedOption) -> Result<WhitelistSubCommand, String> {
+fn parse_rm_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<WhitelistSubCommand, String> {
     let opt = opt.clone();
     if let ResolvedValue::SubCommand(opt) = opt.value {
         if let Some(ResolvedOption { value: ResolvedValue::String(login), .. }) = opt.first() {
-            return Ok(WhitelistSubCommand::Rm { login: login.to_string() })
+            return Ok(WhitelistSubCommand::Rm { caller_discord_id: cmd.user.id, login: login.to_string() })
         }
     }
     Err("Invalid options provided.".to_string())
 }
-fn parse_add_options(opt: &ResolvedOption) -> Result<WhitelistSubCommand, String> {
+fn parse_add_options(opt: &ResolvedOption, cmd: &CommandInteraction) -> Result<WhitelistSubCommand, String> {
     let opt = opt.clone();
     if let ResolvedValue::SubCommand(opt) = opt.value {
         if let Some(ResolvedOption { value: ResolvedValue::String(login), .. }) = opt.first() {
-            return Ok(WhitelistSubCommand::Add { login: login.to_string() })
+            return Ok(WhitelistSubCommand::Add { caller_discord_id: cmd.user.id, login: login.to_string() })
         }
     }
     Err("Invalid options provided.".to_string())
 }
 
-pub fn get_options(options: &Vec<ResolvedOption>) -> Result<WhitelistSubCommand, String> {
+pub fn get_options(options: &Vec<ResolvedOption>, cmd: &CommandInteraction) -> Result<WhitelistSubCommand, String> {
     if options.len() != 1 {
         return Err("Invalid options count".to_string());
     }
@@ -45,20 +47,20 @@ pub fn get_options(options: &Vec<ResolvedOption>) -> Result<WhitelistSubCommand,
     let subcommand = options.first().unwrap();
 
     match subcommand.name {
-        "add" => parse_add_options(subcommand),
-        "rm" => parse_rm_options(subcommand),
+        "add" => parse_add_options(subcommand, cmd),
+        "rm" => parse_rm_options(subcommand, cmd),
         _ => Err("Invalid subcommand.".to_string())
     }
 }
 
-pub async fn execute(cmd: WhitelistSubCommand, database: &PgDatabase) -> CreateInteractionResponseFollowup {
+pub async fn execute(cmd: WhitelistSubCommand, database: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
     match cmd {
-        WhitelistSubCommand::Add { login } => execute_add_cmd(login, database).await,
-        WhitelistSubCommand::Rm { login } => execute_rm_cmd(login, database).await,
+        WhitelistSubCommand::Add { caller_discord_id, login } => execute_add_cmd(caller_discord_id, login, database, config, http).await,
+        WhitelistSubCommand::Rm { caller_discord_id, login } => execute_rm_cmd(caller_discord_id, login, database, config, http).await,
     }
 }
 
-async fn execute_rm_cmd(login: String, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+async fn execute_rm_cmd(caller: UserId, login: String, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
     let uuid = match get_user_id_by_login(&login, db).await {
         Some(id) => id,
         None => return create_response_with_content("No such player found.", true),
@@ -69,6 +71,7 @@ async fn execute_rm_cmd(login: String, db: &PgDatabase) -> CreateInteractionResp
             if rows == 0 {
                 create_response_with_content(&format!("User {} is not whitelisted.", login), true)
             } else {
+                audit::record(AuditEntry::new(caller, "whitelist.rm", &login), http, db, config).await;
                 create_response_with_content(&format!("Successfully removed {} from whitelist.", login), true)
             }
         }
@@ -79,14 +82,17 @@ async fn execute_rm_cmd(login: String, db: &PgDatabase) -> CreateInteractionResp
     }
 }
 
-async fn execute_add_cmd(login: String, db: &PgDatabase) -> CreateInteractionResponseFollowup {
+async fn execute_add_cmd(caller: UserId, login: String, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
     let uuid = match get_user_id_by_login(&login, db).await {
         Some(id) => id,
         None => return create_response_with_content("No such player found.", true),
     };
 
     match db.whitelistadd(&uuid).await {
-        Ok(_) => create_response_with_content(&format!("Successfully added {} to whitelist.", login), true),
+        Ok(_) => {
+            audit::record(AuditEntry::new(caller, "whitelist.add", &login), http, db, config).await;
+            create_response_with_content(&format!("Successfully added {} to whitelist.", login), true)
+        },
         Err(e) => {
             if let Error::SqlxError(sqlx_err) = e {
                 if let Some(db_err) = sqlx_err.into_database_error() {
@@ -101,6 +107,6 @@ async fn execute_add_cmd(login: String, db: &PgDatabase) -> CreateInteractionRes
 }
 
 pub enum WhitelistSubCommand {
-    Add { login: String },
-    Rm { login: String },
+    Add { caller_discord_id: UserId, login: String },
+    Rm { caller_discord_id: UserId, login: String },
 }
\ No newline at end of file
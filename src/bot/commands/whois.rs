@@ -0,0 +1,129 @@
+use log::error;
+use serenity::all::{ChannelId, CommandInteraction, CommandOptionType, CreateAllowedMentions, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseFollowup, CreateMessage, Http, ResolvedOption, ResolvedValue, UserId};
+
+use crate::{api::discord_client::DiscordApiClient, bot::{create_response_with_content, utilities::{generate_random_colour, get_user_id_by_login}}, config::Config, database::PgDatabase};
+
+pub fn get_registration() -> CreateCommand {
+    CreateCommand::new("whois")
+        .description("Shows linked Discord account or in-game login")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::User,
+                "user",
+                "Discord user to look up",
+            )
+            .required(false),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "login",
+                "In-Game login to look up",
+            )
+            .set_autocomplete(true)
+            .required(false),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "ping",
+                "Mention found Discord user in this channel",
+            )
+            .required(false),
+        )
+}
+
+pub fn get_options(options: &[ResolvedOption], cmd: &CommandInteraction) -> Result<WhoisCommand, String> {
+    let mut target: Option<WhoisTarget> = None;
+    let mut ping = false;
+
+    for option in options {
+        match (option.name, &option.value) {
+            ("user", ResolvedValue::User(user, _)) if target.is_none() => target = Some(WhoisTarget::Discord(user.id)),
+            ("login", ResolvedValue::String(login)) if target.is_none() => target = Some(WhoisTarget::Login(login.to_string())),
+            ("user", _) | ("login", _) => return Err("Specify either `user` or `login`, not both.".to_string()),
+            ("ping", ResolvedValue::Boolean(p)) => ping = *p,
+            _ => return Err("Invalid options passed".to_string())
+        }
+    }
+
+    match target {
+        Some(target) => Ok(WhoisCommand {
+            caller_discord_id: cmd.user.id,
+            channel_id: cmd.channel_id,
+            target,
+            ping,
+        }),
+        None => Err("Specify either `user` or `login`.".to_string()),
+    }
+}
+
+pub async fn execute(cmd: WhoisCommand, db: &PgDatabase, config: &Config, http: &Http) -> CreateInteractionResponseFollowup {
+    let auth_client = match DiscordApiClient::new(config.auth_url(), config.auth_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating auth client: {e}");
+            return create_response_with_content("Unable to setup authorization client.", true);
+        }
+    };
+
+    let (discord_id, login) = match cmd.target {
+        WhoisTarget::Discord(discord_id) => {
+            let uuid = match auth_client.uuid(&discord_id.get().to_string()).await {
+                Some(uuid) => uuid,
+                None => return create_response_with_content(&format!("<@{}> has no linked SS14 account.", discord_id), true),
+            };
+
+            match db.get_login_by_uuid(&uuid).await {
+                Ok(Some(login)) => (discord_id, login),
+                Ok(None) => return create_response_with_content(&format!("<@{}> is linked to `{}`, but never played here.", discord_id, uuid), true),
+                Err(e) => {
+                    error!("Error fetching login for {}: {}", uuid, e);
+                    return create_response_with_content("Unable to fetch in-game login.", true);
+                }
+            }
+        },
+        WhoisTarget::Login(login) => {
+            let uuid = match get_user_id_by_login(&login, db).await {
+                Some(uuid) => uuid,
+                None => return create_response_with_content("No such player found.", true),
+            };
+
+            match auth_client.discord_id(&uuid).await {
+                Some(discord_id) => (discord_id, login),
+                None => return create_response_with_content(&format!("`{}` has no linked Discord account.", login), true),
+            }
+        }
+    };
+
+    if cmd.ping {
+        let message = CreateMessage::new()
+            .content(format!("<@{}>, you are called by <@{}>.", discord_id, cmd.caller_discord_id))
+            .allowed_mentions(CreateAllowedMentions::new().users(vec![discord_id]));
+
+        if let Err(e) = cmd.channel_id.send_message(http, message).await {
+            error!("Error pinging {}: {}", discord_id, e);
+            return create_response_with_content("Found the user, but unable to ping them in this channel.", true);
+        }
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!("Who is `{}`", login))
+        .description(format!("🎮 **In-Game Login:** {}\n💬 **Discord:** <@{}>", login, discord_id))
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+    CreateInteractionResponseFollowup::new().add_embed(embed).ephemeral(true)
+}
+
+pub enum WhoisTarget {
+    Discord(UserId),
+    Login(String),
+}
+
+pub struct WhoisCommand {
+    caller_discord_id: UserId,
+    channel_id: ChannelId,
+    target: WhoisTarget,
+    ping: bool,
+}
@@ -0,0 +1,102 @@
+use std::{collections::HashMap, sync::Mutex, time::{Duration, Instant}};
+
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, UserId};
+use uuid::Uuid;
+
+use crate::{api::ss14client::{BanRequest, EditBanRequest, PardonRequest, RoleBanRequest, RoundRequest}, bot::{audit::AuditEntry, commands::round::RoundAction}, database::{NewServerRoleBan, ServerBanEdit}};
+
+pub static CONFIRM_PREFIX: &str = "confirm";
+pub static CANCEL_PREFIX: &str = "cancel";
+
+/// Action waiting for invoking admin to press Confirm.
+pub enum PendingAction {
+    Ban {
+        login: String,
+        request: BanRequest,
+        audit: AuditEntry,
+    },
+    Pardon {
+        id: i32,
+        request: PardonRequest,
+        audit: AuditEntry,
+    },
+    EditBan {
+        id: i32,
+        request: EditBanRequest,
+        edit: ServerBanEdit, // written to database if SS14 api can't edit bans
+        audit: AuditEntry,
+    },
+    RoleBan {
+        login: String,
+        request: RoleBanRequest,
+        ban: NewServerRoleBan, // written to database if SS14 api can't role ban
+        audit: AuditEntry,
+    },
+    RolePardon {
+        id: i32,
+        admin: Uuid,
+        request: PardonRequest,
+        audit: AuditEntry,
+    },
+    Round {
+        action: RoundAction,
+        request: RoundRequest,
+        audit: AuditEntry,
+    },
+}
+
+struct PendingConfirmation {
+    owner: UserId,
+    created_at: Instant,
+    action: PendingAction,
+}
+
+pub struct Confirmations {
+    timeout: Duration,
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+}
+
+impl Confirmations {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, pending: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Stores action and returns Confirm/Cancel buttons bound to it.
+    pub fn add(&self, owner: UserId, action: PendingAction) -> Vec<CreateActionRow> {
+        let id = format!("{:016x}", rand::random::<u64>());
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, p| p.created_at.elapsed() < self.timeout);
+        pending.insert(id.clone(), PendingConfirmation { owner, created_at: Instant::now(), action });
+
+        vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(format!("{}:{}", CONFIRM_PREFIX, id))
+                .label("Confirm")
+                .style(ButtonStyle::Danger),
+            CreateButton::new(format!("{}:{}", CANCEL_PREFIX, id))
+                .label("Cancel")
+                .style(ButtonStyle::Secondary),
+        ])]
+    }
+
+    /// Removes pending action if `user` is the one who requested it and it has not expired yet.
+    pub fn take(&self, id: &str, user: UserId) -> Result<PendingAction, String> {
+        let mut pending = self.pending.lock().unwrap();
+        match pending.get(id) {
+            None => return Err("This action has expired or was already handled.".to_string()),
+            Some(p) if p.owner != user => return Err("Only the invoking admin can do this.".to_string()),
+            Some(_) => {}
+        }
+
+        let confirmation = pending.remove(id).unwrap();
+        if confirmation.created_at.elapsed() >= self.timeout {
+            return Err("This action has expired or was already handled.".to_string());
+        }
+
+        Ok(confirmation.action)
+    }
+}
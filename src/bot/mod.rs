@@ -1,58 +1,133 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::atomic::{AtomicBool, Ordering}, time::Duration};
 
-use commands::{ban, notes, whitelist, DiscordCommandType};
+use confirmation::{Confirmations, PendingAction};
+use pagination::SavedQueries;
+
+use commands::{achievements, alts, ban, connections, gamerules, kick, notes, player, roleban, round, status, whitelist, whois, DiscordCommandType, DiscordComponentType, DiscordModalType};
 use log::{
     debug,
     info,
     error
 };
 
-use serenity::{all::{CommandInteraction, Context, CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage, EventHandler, GatewayIntents, GuildId, Http, Interaction, Ready}, async_trait, Client};
+use serenity::{all::{AutocompleteChoice, CommandInteraction, ComponentInteraction, Context, CreateAutocompleteResponse, CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage, EditInteractionResponse, EventHandler, GatewayIntents, GuildId, Http, Interaction, Message, ModalInteraction, Ready}, async_trait, Client};
+
+use utilities::resolve_user_name;
+use uuid::Uuid;
 
-use crate::{config::Config, database::PgDatabase, error::Error};
+use crate::{api::ss14client::SS14ApiClient, config::Config, database::PgDatabase, error::Error};
 
 pub mod utilities;
 pub mod commands;
+pub mod permissions;
+pub mod pagination;
+pub mod confirmation;
+pub mod audit;
+pub mod ahelp;
+pub mod tasks;
+
+// discord doesn't accept more choices
+static MAX_AUTOCOMPLETE_CHOICES: i64 = 25;
 
 pub struct DiscordBot {
     config: Config,
     db: PgDatabase, // ss14 database connection
+    tasks_started: AtomicBool, // ready is fired again on every reconnect
+    confirmations: Confirmations,
+    ban_searches: SavedQueries<ban::BanSearch>,
 }
 
 #[async_trait]
 impl EventHandler for DiscordBot {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(command) = interaction {
-            debug!("Recieved command interaction: {} {:?}", command.data.name, command.data.options);
-            self.handle_command_interaction(ctx, command).await;
+        match interaction {
+            Interaction::Command(command) => {
+                debug!("Recieved command interaction: {} {:?}", command.data.name, command.data.options);
+                self.handle_command_interaction(ctx, command).await;
+            },
+            Interaction::Autocomplete(autocomplete) => {
+                self.handle_autocomplete_interaction(ctx, autocomplete).await;
+            },
+            Interaction::Component(component) => {
+                debug!("Recieved component interaction: {}", component.data.custom_id);
+                self.handle_component_interaction(ctx, component).await;
+            },
+            Interaction::Modal(modal) => {
+                debug!("Recieved modal interaction: {}", modal.data.custom_id);
+                self.handle_modal_interaction(ctx, modal).await;
+            },
+            _ => {}
         }
     }
 
+    async fn message(&self, ctx: Context, msg: Message) {
+        if msg.author.bot || msg.content.is_empty() || msg.guild_id.is_none() {
+            return;
+        }
+        let ahelp_channel = match self.config.ahelp_channel() {
+            Some(channel) => channel,
+            None => return,
+        };
+        if !ahelp::is_ahelp_thread(&msg, &ctx, ahelp_channel).await {
+            return;
+        }
+
+        ahelp::relay_to_game(&msg, &ctx.http, &self.db, &self.config).await;
+    }
+
     async fn ready(&self, ctx: Context, ready: Ready) {
         info!("Bot {} connected and ready to handle interactions!", ready.user.name);
         let result = Self::register_commands(&ctx.http, self.config.guild(), vec![
             whitelist::get_registration(),
             notes::get_registration(),
             ban::get_registration(),
+            kick::get_registration(),
+            roleban::get_registration(),
+            whois::get_registration(),
+            achievements::get_registration(),
+            commands::audit::get_registration(),
+            status::get_registration(),
+            round::get_registration(),
+            gamerules::get_registration(),
+            player::get_registration(),
+            connections::get_registration(),
+            alts::get_registration(),
         ]).await;
 
         if let Err(e) = result {
             error!("Error happened registering interaction commands: {}", e);
         }
+
+        if !self.tasks_started.swap(true, Ordering::SeqCst) {
+            tasks::achievements::spawn_worker(ctx.http.clone(), self.db.clone(), self.config.clone());
+            tasks::ban_announcements::spawn_worker(ctx.http.clone(), self.db.clone(), self.config.clone());
+            tasks::presence::spawn_worker(ctx.clone(), self.config.clone());
+        }
     }
 }
 
 impl DiscordBot {
-    pub fn new(config: &Config) -> Result<Self, Error> {
-        let db = PgDatabase::new(config.cstr())?;
-        info!("Connected to SS14 database.");
-        Ok(Self { db, config: config.clone() })
+    pub fn new(config: &Config, db: PgDatabase) -> Self {
+        Self {
+            db,
+            config: config.clone(),
+            tasks_started: AtomicBool::new(false),
+            confirmations: Confirmations::new(Duration::from_secs(config.confirmation_timeout())),
+            ban_searches: SavedQueries::new(ban::SEARCH_TTL),
+        }
     }
 
     pub async fn start(self) {
         let token = self.config.token();
 
-        let mut client = Client::builder(token, GatewayIntents::empty())
+        // message content is privileged, so it's requested only when ahelp relay needs it
+        // guilds fills the channel cache ahelp relay checks every message against
+        let intents = match self.config.ahelp_channel() {
+            Some(_) => GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT,
+            None => GatewayIntents::empty(),
+        };
+
+        let mut client = Client::builder(token, intents)
             .event_handler(self).await.expect("Unable to create serenity client");
 
         if let Err(e) = client.start().await {
@@ -77,6 +152,16 @@ impl DiscordBot {
             return;
         }
 
+        // modal has to be the first response, so it's sent before defer and permissions are checked on submit
+        if let Ok(DiscordCommandType::Ban) = command_type {
+            if let Some(modal) = ban::get_ban_modal(&command.data.options(), &self.config) {
+                if let Err(e) = command.create_response(&ctx.http, CreateInteractionResponse::Modal(modal)).await {
+                    error!("Error creating modal response: {e}");
+                }
+                return;
+            }
+        }
+
         if let Err(e) = command.create_response(&ctx.http, CreateInteractionResponse::Defer(
             CreateInteractionResponseMessage::new().content("Your request is processing...").ephemeral(true)
         )).await {
@@ -84,26 +169,105 @@ impl DiscordBot {
             return;
         }
 
+        if let Err(e) = permissions::check_permissions(&command, &self.db, &self.config).await {
+            debug!("Refused {} for {}: {}", permissions::permission_key(&command), command.user.id, e);
+            if let Err(e) = command.create_followup(&ctx.http, create_response_with_content(&e, true)).await {
+                error!("Error creating response: {e}");
+            }
+            return;
+        }
+
         let command_type = command_type.unwrap();
         let response = match command_type {
             DiscordCommandType::Whitelist => {
-                let result = whitelist::get_options(&command.data.options());
+                let result = whitelist::get_options(&command.data.options(), &command);
                 match result {
-                    Ok(options) => whitelist::execute(options, &self.db).await,
+                    Ok(options) => whitelist::execute(options, &self.db, &self.config, &ctx.http).await,
                     Err(e) => create_response_with_content(&e, true)
                 }
             },
             DiscordCommandType::Notes => {
-                let result = notes::get_options(&command.data.options());
+                let result = notes::get_options(&command.data.options(), &command);
                 match result {
-                    Ok(options) => notes::execute(options, &self.db).await,
+                    Ok(options) => notes::execute(options, &self.db, &self.config, &ctx.http).await,
                     Err(e) => create_response_with_content(&e, true)
                 }
             }
             DiscordCommandType::Ban => {
-                let result = ban::get_options(&command.data.options(), &command);
+                let result = ban::get_options(&command.data.options(), &command, &self.config);
+                match result {
+                    Ok(options) => ban::execute(options, &self.db, &self.config, &self.confirmations, &self.ban_searches).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Kick => {
+                let result = kick::get_options(&command.data.options(), &command);
+                match result {
+                    Ok(options) => kick::execute(options, &self.db, &self.config, &ctx.http).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::RoleBan => {
+                let result = roleban::get_options(&command.data.options(), &command);
+                match result {
+                    Ok(options) => roleban::execute(options, &self.db, &self.config, &self.confirmations).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Whois => {
+                let result = whois::get_options(&command.data.options(), &command);
+                match result {
+                    Ok(options) => whois::execute(options, &self.db, &self.config, &ctx.http).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Achievements => {
+                let result = achievements::get_options(&command.data.options(), &command);
+                match result {
+                    Ok(options) => achievements::execute(options, &self.db, &self.config, ctx.http.clone()).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Audit => {
+                let result = commands::audit::get_options(&command.data.options());
+                match result {
+                    Ok(options) => commands::audit::execute(options, &self.db).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Status => status::execute(&self.config).await,
+            DiscordCommandType::Round => {
+                let result = round::get_options(&command.data.options(), &command);
+                match result {
+                    Ok(options) => round::execute(options, &self.db, &self.config, &self.confirmations, &ctx.http).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::GameRules => {
+                let result = gamerules::get_options(&command.data.options(), &command);
+                match result {
+                    Ok(options) => gamerules::execute(options, &self.db, &self.config, &ctx.http).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Player => {
+                let result = player::get_options(&command.data.options());
+                match result {
+                    Ok(options) => player::execute(options, &self.db).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Connections => {
+                let result = connections::get_options(&command.data.options());
+                match result {
+                    Ok(options) => connections::execute(options, &self.db).await,
+                    Err(e) => create_response_with_content(&e, true)
+                }
+            }
+            DiscordCommandType::Alts => {
+                let result = alts::get_options(&command.data.options());
                 match result {
-                    Ok(options) => ban::execute(options, &self.db, &self.config).await,
+                    Ok(login) => alts::execute(login, &self.db).await,
                     Err(e) => create_response_with_content(&e, true)
                 }
             }
@@ -113,6 +277,221 @@ impl DiscordBot {
             error!("Error creating response: {e}");
         }
     }
+
+    async fn execute_pending_action(&self, action: PendingAction, http: &Http) -> String {
+        match action {
+            PendingAction::Ban { login, request, audit } => ban::execute_confirmed_ban(login, request, audit, &self.db, &self.config, http).await,
+            PendingAction::Pardon { id, request, audit } => ban::execute_confirmed_pardon(id, request, audit, &self.db, &self.config, http).await,
+            PendingAction::EditBan { id, request, edit, audit } => ban::execute_confirmed_edit(id, request, edit, audit, &self.db, &self.config, http).await,
+            PendingAction::RoleBan { login, request, ban, audit } => roleban::execute_confirmed_ban(login, request, ban, audit, &self.db, &self.config, http).await,
+            PendingAction::RolePardon { id, admin, request, audit } => roleban::execute_confirmed_pardon(id, admin, request, audit, &self.db, &self.config, http).await,
+            PendingAction::Round { action, request, audit } => round::execute_confirmed_action(action, request, audit, &self.db, &self.config, http).await,
+        }
+    }
+
+    async fn handle_autocomplete_interaction(&self, ctx: Context, autocomplete: CommandInteraction) {
+        let focused = match autocomplete.data.autocomplete() {
+            Some(option) => option,
+            None => return,
+        };
+
+        let choices = match focused.name {
+            "login" | "admin" => match self.db.search_logins(focused.value, MAX_AUTOCOMPLETE_CHOICES).await {
+                Ok(logins) => logins.into_iter().map(|login| AutocompleteChoice::new(login.clone(), login)).collect(),
+                Err(e) => {
+                    error!("Error searching logins for autocomplete: {e}");
+                    vec![]
+                }
+            },
+            "template" => {
+                let query = focused.value.to_lowercase();
+                self.config.ban_templates()
+                    .iter()
+                    .filter(|t| t.name.to_lowercase().contains(&query))
+                    .take(MAX_AUTOCOMPLETE_CHOICES as usize)
+                    .map(|t| AutocompleteChoice::new(t.name.clone(), t.name.clone()))
+                    .collect()
+            },
+            "rule" => {
+                let query = focused.value.to_lowercase();
+                let rules = match SS14ApiClient::new(self.config.api_url(), self.config.server_token()) {
+                    Ok(client) => client.game_rules().await,
+                    Err(e) => Err(e),
+                };
+
+                match rules {
+                    Ok(rules) => rules.into_iter()
+                        .filter(|rule| rule.to_lowercase().contains(&query))
+                        .take(MAX_AUTOCOMPLETE_CHOICES as usize)
+                        .map(|rule| AutocompleteChoice::new(rule.clone(), rule))
+                        .collect(),
+                    Err(e) => {
+                        error!("Error fetching game rules for autocomplete: {e}");
+                        vec![]
+                    }
+                }
+            },
+            _ => vec![],
+        };
+
+        let response = CreateInteractionResponse::Autocomplete(CreateAutocompleteResponse::new().set_choices(choices));
+        if let Err(e) = autocomplete.create_response(&ctx.http, response).await {
+            error!("Error creating autocomplete response: {e}");
+        }
+    }
+
+    async fn handle_component_interaction(&self, ctx: Context, component: ComponentInteraction) {
+        let prefix = component.data.custom_id.split(':').next().unwrap_or_default();
+        let component_type = match DiscordComponentType::from_str(prefix) {
+            Ok(t) => t,
+            Err(_) => {
+                error!("Invalid component interaction provided: {}", component.data.custom_id);
+                create_component_response("Invalid interaction type!", ctx, component).await;
+                return;
+            }
+        };
+
+        let response = match component_type {
+            DiscordComponentType::BansList | DiscordComponentType::NotesList | DiscordComponentType::RoleBansList => {
+                let key = match component_type {
+                    DiscordComponentType::BansList => "bans.list",
+                    DiscordComponentType::RoleBansList => "rolebans.list",
+                    _ => "notes.list",
+                };
+                if let Err(e) = permissions::check_key_permissions(key, component.user.id, &self.db, &self.config).await {
+                    return create_component_response(&e, ctx, component).await;
+                }
+
+                let (page, uuid) = match pagination::parse_page_id(&component.data.custom_id) {
+                    Some((_, page, key)) => match Uuid::parse_str(key) {
+                        Ok(uuid) => (page, uuid),
+                        Err(_) => return create_component_response("Invalid page.", ctx, component).await,
+                    },
+                    None => return create_component_response("Invalid page.", ctx, component).await,
+                };
+                let login = resolve_user_name(&self.db, &uuid).await;
+
+                let result = match component_type {
+                    DiscordComponentType::BansList => ban::list_page(&uuid, &login, page, &self.db).await,
+                    DiscordComponentType::RoleBansList => roleban::list_page(&uuid, &login, page, &self.db).await,
+                    _ => notes::list_page(&uuid, &login, page, &self.db).await,
+                };
+
+                match result {
+                    Ok((embed, components)) => CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new().embed(embed).components(components)
+                    ),
+                    Err(e) => return create_component_response(&e, ctx, component).await,
+                }
+            },
+            DiscordComponentType::BansSearch => {
+                if let Err(e) = permissions::check_key_permissions("bans.search", component.user.id, &self.db, &self.config).await {
+                    return create_component_response(&e, ctx, component).await;
+                }
+
+                let (page, key) = match pagination::parse_page_id(&component.data.custom_id) {
+                    Some((_, page, key)) => (page, key),
+                    None => return create_component_response("Invalid page.", ctx, component).await,
+                };
+                let search = match self.ban_searches.get(key) {
+                    Some(search) => search,
+                    None => return create_component_response("This search has expired, run it again.", ctx, component).await,
+                };
+
+                match ban::search_page(&search, key, page, &self.db).await {
+                    Ok((embed, components)) => CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new().embed(embed).components(components)
+                    ),
+                    Err(e) => return create_component_response(&e, ctx, component).await,
+                }
+            },
+            DiscordComponentType::ConnectionsList => {
+                if let Err(e) = permissions::check_key_permissions("connections", component.user.id, &self.db, &self.config).await {
+                    return create_component_response(&e, ctx, component).await;
+                }
+
+                let (page, query) = match pagination::parse_page_id(&component.data.custom_id) {
+                    Some((_, page, key)) => match connections::ConnectionsQuery::from_key(key) {
+                        Some(query) => (page, query),
+                        None => return create_component_response("Invalid page.", ctx, component).await,
+                    },
+                    None => return create_component_response("Invalid page.", ctx, component).await,
+                };
+                let login = resolve_user_name(&self.db, query.user_id()).await;
+
+                match connections::list_page(&query, &login, page, &self.db).await {
+                    Ok((embed, components)) => CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new().embed(embed).components(components)
+                    ),
+                    Err(e) => return create_component_response(&e, ctx, component).await,
+                }
+            },
+            DiscordComponentType::Confirm | DiscordComponentType::Cancel => {
+                let id = component.data.custom_id.split_once(':').map(|(_, id)| id).unwrap_or_default();
+                let action = match self.confirmations.take(id, component.user.id) {
+                    Ok(action) => action,
+                    Err(e) => return create_component_response(&e, ctx, component).await,
+                };
+
+                if let DiscordComponentType::Cancel = component_type {
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new().content("Cancelled.").embeds(vec![]).components(vec![])
+                    )
+                } else {
+                    // game server may answer slower than interaction timeout
+                    if let Err(e) = component.create_response(&ctx.http, CreateInteractionResponse::Acknowledge).await {
+                        error!("Error acknowledging confirmation: {e}");
+                        return;
+                    }
+
+                    let result = self.execute_pending_action(action, &ctx.http).await;
+                    let edit = EditInteractionResponse::new().content(result).embeds(vec![]).components(vec![]);
+                    if let Err(e) = component.edit_response(&ctx.http, edit).await {
+                        error!("Error updating message: {e}");
+                    }
+                    return;
+                }
+            }
+        };
+
+        if let Err(e) = component.create_response(&ctx.http, response).await {
+            error!("Error updating message: {e}");
+        }
+    }
+
+    async fn handle_modal_interaction(&self, ctx: Context, modal: ModalInteraction) {
+        let prefix = modal.data.custom_id.split(':').next().unwrap_or_default();
+        let modal_type = match DiscordModalType::from_str(prefix) {
+            Ok(t) => t,
+            Err(_) => {
+                error!("Invalid modal interaction provided: {}", modal.data.custom_id);
+                return;
+            }
+        };
+
+        if let Err(e) = modal.create_response(&ctx.http, CreateInteractionResponse::Defer(
+            CreateInteractionResponseMessage::new().ephemeral(true)
+        )).await {
+            error!("Error creating defer response: {e}");
+            return;
+        }
+
+        let response = match modal_type {
+            DiscordModalType::Ban => {
+                match permissions::check_key_permissions("bans.ban", modal.user.id, &self.db, &self.config).await {
+                    Ok(_) => match ban::parse_ban_modal(&modal) {
+                        Ok(options) => ban::execute(options, &self.db, &self.config, &self.confirmations, &self.ban_searches).await,
+                        Err(e) => create_response_with_content(&e, true),
+                    },
+                    Err(e) => create_response_with_content(&e, true),
+                }
+            }
+        };
+
+        if let Err(e) = modal.create_followup(&ctx.http, response).await {
+            error!("Error creating response: {e}");
+        }
+    }
 }
 
 fn create_response_with_content(s: &str, eph: bool) -> CreateInteractionResponseFollowup {
@@ -124,4 +503,11 @@ async fn create_response(s: &str, ctx: Context, command: CommandInteraction) {
     if let Err(e) = command.create_response(&ctx.http, builder).await {
         error!("Error creating response: {e}");
     }
+}
+
+async fn create_component_response(s: &str, ctx: Context, component: ComponentInteraction) {
+    let builder = CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(s).ephemeral(true));
+    if let Err(e) = component.create_response(&ctx.http, builder).await {
+        error!("Error creating response: {e}");
+    }
 }
\ No newline at end of file
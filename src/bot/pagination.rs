@@ -0,0 +1,74 @@
+use std::{collections::HashMap, sync::Mutex, time::{Duration, Instant}};
+
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton};
+
+pub static PER_PAGE: i64 = 10;
+
+pub fn pages_count(total: i64) -> i64 {
+    ((total + PER_PAGE - 1) / PER_PAGE).max(1)
+}
+
+/// Custom id of a button opening `page` of a list.
+pub fn page_id(prefix: &str, page: i64, key: &str) -> String {
+    format!("{}:{}:{}", prefix, page, key)
+}
+
+/// Builds Previous/Next buttons, their custom ids are `prefix:page:key`.
+pub fn page_buttons(prefix: &str, key: &str, page: i64, pages: i64) -> Vec<CreateActionRow> {
+    if pages <= 1 {
+        return vec![];
+    }
+
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(page_id(prefix, page - 1, key))
+            .label("◀ Previous")
+            .style(ButtonStyle::Secondary)
+            .disabled(page <= 0),
+        CreateButton::new(page_id(prefix, page + 1, key))
+            .label("Next ▶")
+            .style(ButtonStyle::Secondary)
+            .disabled(page + 1 >= pages),
+    ])]
+}
+
+/// Splits `prefix:page:key` custom id back into its parts.
+pub fn parse_page_id(custom_id: &str) -> Option<(&str, i64, &str)> {
+    let mut parts = custom_id.splitn(3, ':');
+    let prefix = parts.next()?;
+    let page = parts.next()?.parse().ok()?;
+    let key = parts.next()?;
+
+    Some((prefix, page, key))
+}
+
+/// Keeps queries too long for a custom id, page buttons refer to them by short key.
+/// Queries are forgotten after `ttl` or bot restart, so the search has to be run again.
+pub struct SavedQueries<T> {
+    ttl: Duration,
+    queries: Mutex<HashMap<String, (Instant, T)>>,
+}
+
+impl<T: Clone> SavedQueries<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, queries: Mutex::new(HashMap::new()) }
+    }
+
+    /// Stores query and returns its key.
+    pub fn add(&self, query: T) -> String {
+        let key = format!("{:016x}", rand::random::<u64>());
+
+        let mut queries = self.queries.lock().unwrap();
+        queries.retain(|_, (created_at, _)| created_at.elapsed() < self.ttl);
+        queries.insert(key.clone(), (Instant::now(), query));
+
+        key
+    }
+
+    pub fn get(&self, key: &str) -> Option<T> {
+        let queries = self.queries.lock().unwrap();
+        match queries.get(key) {
+            Some((created_at, query)) if created_at.elapsed() < self.ttl => Some(query.clone()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+
+use log::error;
+use serenity::all::{CommandDataOptionValue, CommandInteraction, UserId};
+use uuid::Uuid;
+
+use crate::{api::discord_client::DiscordApiClient, bot::utilities::{format_duration, parse_duration}, config::Config, database::PgDatabase, error::Error};
+
+// flag required when neither config nor defaults know the command
+static FALLBACK_FLAG: &str = "ADMIN";
+
+/// Effective SS14 admin permissions: rank flags plus positive admin flags, minus negative ones.
+/// Suspended and deadminned admins have no flags.
+#[derive(Debug, Clone)]
+pub struct AdminPermissions {
+    pub user_id: Uuid,
+    pub rank: Option<String>,
+    pub flags: HashSet<String>,
+}
+
+impl AdminPermissions {
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(&flag.to_uppercase())
+    }
+}
+
+// None means the command is public
+fn default_flag(key: &str) -> Option<&'static str> {
+    let flag = match key {
+        "whitelist.add" | "whitelist.rm" => "BAN",
+        "notes.list" | "notes.note" => "VIEWNOTES",
+        "notes.add" | "notes.edit" | "notes.delete" => "EDITNOTES",
+        "bans.list" | "bans.info" | "bans.ban" | "bans.pardon" | "bans.edit" | "bans.search" => "BAN",
+        "rolebans.list" | "rolebans.info" | "rolebans.ban" | "rolebans.pardon" => "BAN",
+        "kick" | "whois" | "achievements.sync" | "audit.search" | "player.info" | "connections" | "alts" => "ADMIN",
+        "ahelp.reply" => "ADMINHELP",
+        "round.start" | "round.end" | "round.restart" | "round.preset" => "ROUND",
+        "gamerules.list" | "gamerules.add" | "gamerules.end" => "ROUND",
+        "status" => return None,
+        _ => FALLBACK_FLAG,
+    };
+
+    Some(flag)
+}
+
+/// Builds the permission key of a command: `command.subcommand`, or just `command` without subcommands.
+pub fn permission_key(command: &CommandInteraction) -> String {
+    match command.data.options.first() {
+        Some(option) if matches!(option.value, CommandDataOptionValue::SubCommand(_) | CommandDataOptionValue::SubCommandGroup(_)) => {
+            format!("{}.{}", command.data.name, option.name)
+        },
+        _ => command.data.name.to_string(),
+    }
+}
+
+pub fn required_flag(key: &str, config: &Config) -> Option<String> {
+    match config.permissions().get(key) {
+        Some(flag) => flag.clone(),
+        None => default_flag(key).map(|flag| flag.to_string()),
+    }
+}
+
+pub async fn load_permissions(user_id: &Uuid, db: &PgDatabase) -> Result<Option<AdminPermissions>, Error> {
+    let admin = match db.get_admin(user_id).await? {
+        Some(admin) => admin,
+        None => return Ok(None),
+    };
+
+    let mut flags = HashSet::new();
+    // same as in-game, suspended or deadminned admin has no permissions at all
+    if admin.suspended || admin.deadminned {
+        return Ok(Some(AdminPermissions { user_id: admin.user_id, rank: admin.rank_name, flags }));
+    }
+
+    if let Some(rank_id) = admin.admin_rank_id {
+        for flag in db.get_admin_rank_flags(rank_id).await? {
+            flags.insert(flag.to_uppercase());
+        }
+    }
+
+    let admin_flags = db.get_admin_flags(user_id).await?;
+    for flag in admin_flags.iter().filter(|f| !f.negative) {
+        flags.insert(flag.flag.to_uppercase());
+    }
+    for flag in admin_flags.iter().filter(|f| f.negative) {
+        flags.remove(&flag.flag.to_uppercase());
+    }
+
+    Ok(Some(AdminPermissions {
+        user_id: admin.user_id,
+        rank: admin.rank_name,
+        flags,
+    }))
+}
+
+/// Resolves the caller through typeauthd and loads their SS14 admin permissions.
+pub async fn resolve_caller_permissions(caller: UserId, db: &PgDatabase, config: &Config) -> Result<AdminPermissions, String> {
+    let discord_api = match DiscordApiClient::new(config.auth_url(), config.auth_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating auth client: {e}");
+            return Err("Unable to setup authorization client.".to_string());
+        }
+    };
+
+    let user_id = match discord_api.uuid(&caller.get().to_string()).await {
+        Some(uuid) => uuid,
+        None => return Err("You're probably unauthorized to perform this action.".to_string()),
+    };
+
+    match load_permissions(&user_id, db).await {
+        Ok(Some(permissions)) => Ok(permissions),
+        Ok(None) => Err("You're not an admin at SS14 server.".to_string()),
+        Err(e) => {
+            error!("Error loading admin permissions for {}: {}", user_id, e);
+            Err("Unable to load your admin permissions.".to_string())
+        }
+    }
+}
+
+/// Checks that caller is allowed to run the command, returns a user-facing reason otherwise.
+pub async fn check_permissions(command: &CommandInteraction, db: &PgDatabase, config: &Config) -> Result<(), String> {
+    check_key_permissions(&permission_key(command), command.user.id, db, config).await
+}
+
+/// Same as [`check_permissions`] for interactions that are not slash commands, e.g. modal submits.
+pub async fn check_key_permissions(key: &str, caller: UserId, db: &PgDatabase, config: &Config) -> Result<(), String> {
+    let flag = match required_flag(key, config) {
+        Some(flag) => flag,
+        None => return Ok(()),
+    };
+
+    let permissions = resolve_caller_permissions(caller, db, config).await?;
+    if permissions.has_flag(&flag) {
+        Ok(())
+    } else {
+        Err(format!("You need `{}` flag to use this command.", flag.to_uppercase()))
+    }
+}
+
+/// Refuses bans longer than `max_ban_durations` entry of admin's rank allows.
+pub async fn check_duration_limit(admin_uuid: &Uuid, minutes: i64, db: &PgDatabase, config: &Config) -> Result<(), String> {
+    let rank = match load_permissions(admin_uuid, db).await {
+        Ok(Some(permissions)) => permissions.rank,
+        Ok(None) => None,
+        Err(e) => {
+            error!("Error loading admin permissions for {}: {}", admin_uuid, e);
+            return Err("Unable to load your admin permissions.".to_string());
+        }
+    };
+
+    let limit = match rank.as_deref().and_then(|rank| config.max_ban_duration(rank)) {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    let max_minutes = match parse_duration(limit) {
+        Ok(max_minutes) => max_minutes,
+        Err(e) => {
+            error!("Invalid max ban duration configured for rank {:?}: {}", rank, e);
+            return Err("Ban duration limit for your rank is misconfigured.".to_string());
+        }
+    };
+
+    // 0 is permanent on both sides
+    if max_minutes != 0 && (minutes == 0 || minutes > max_minutes) {
+        return Err(format!("Your rank can ban for at most {}.", format_duration(max_minutes)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use std::{collections::{HashMap, HashSet}, sync::Arc, time::Duration};
+
+use log::{debug, error, info, warn};
+use serenity::all::{GuildId, Http, Member, RoleId};
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::{api::discord_client::DiscordApiClient, config::Config, database::PgDatabase, error::Error};
+
+// discord returns at most this many members per request
+static MEMBERS_PAGE_SIZE: u64 = 1000;
+
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub players: usize,
+    pub granted: usize,
+    pub revoked: usize,
+}
+
+pub fn spawn_worker(http: Arc<Http>, db: PgDatabase, config: Config) {
+    if config.achievements().is_empty() {
+        info!("No achievement rules configured, playtime role sync is disabled.");
+        return;
+    }
+    if config.achievements_interval() == 0 {
+        info!("Achievements interval is 0, playtime role sync is disabled.");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.achievements_interval()));
+        loop {
+            interval.tick().await;
+            match sync(&http, &db, &config, None).await {
+                Ok(report) => info!("Achievements synced: {:?}", report),
+                Err(e) => error!("Error syncing achievements: {e}"),
+            }
+        }
+    });
+}
+
+/// How far a running sync got, `checked` out of `total` linked guild members.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncProgress {
+    pub checked: usize,
+    pub total: usize,
+}
+
+/// Grants or revokes achievement roles of every linked guild member according to their playtime.
+/// Guild members are fetched once and resolved through a single batched auth lookup.
+pub async fn sync(http: &Http, db: &PgDatabase, config: &Config, progress: Option<&watch::Sender<SyncProgress>>) -> Result<SyncReport, Error> {
+    let rules = config.achievements();
+    let mut report = SyncReport::default();
+    if rules.is_empty() {
+        return Ok(report);
+    }
+
+    let guild_id = match config.guild().parse::<u64>() {
+        Ok(id) if id != 0 => GuildId::new(id),
+        _ => return Err(Error::ConfigErr(format!("Invalid guild_id `{}`", config.guild()))),
+    };
+    let auth_client = DiscordApiClient::new(config.auth_url(), config.auth_token())?;
+
+    let members = fetch_members(http, guild_id).await?;
+    let member_ids = members.iter().map(|m| m.user.id).collect::<Vec<_>>();
+    let linked = auth_client.uuids(&member_ids).await;
+    debug!("{} of {} guild members have linked SS14 account", linked.len(), members.len());
+
+    let trackers = rules.iter().map(|r| r.tracker.clone()).collect::<HashSet<_>>().into_iter().collect::<Vec<_>>();
+    let mut play_times: HashMap<Uuid, HashMap<String, i64>> = HashMap::new();
+    for play_time in db.get_play_times(&trackers).await? {
+        play_times.entry(play_time.player_id).or_default().insert(play_time.tracker, play_time.seconds);
+    }
+
+    let managed_roles = rules.iter().map(|r| r.role_id).collect::<HashSet<RoleId>>();
+    let no_play_time = HashMap::new();
+    let mut current_progress = SyncProgress { checked: 0, total: linked.len() };
+    if let Some(progress) = progress {
+        progress.send_replace(current_progress);
+    }
+
+    for member in members {
+        let player = match linked.get(&member.user.id) {
+            Some(player) => player,
+            None => continue,
+        };
+        report.players += 1;
+
+        let trackers = play_times.get(player).unwrap_or(&no_play_time);
+        let earned = rules.iter()
+            .filter(|r| trackers.get(&r.tracker).is_some_and(|seconds| *seconds >= r.hours as i64 * 3600))
+            .map(|r| r.role_id)
+            .collect::<HashSet<RoleId>>();
+        let current = member.roles.iter().copied().filter(|r| managed_roles.contains(r)).collect::<HashSet<RoleId>>();
+
+        for role in earned.difference(&current) {
+            match member.add_role(http, *role).await {
+                Ok(_) => report.granted += 1,
+                Err(e) => warn!("Unable to grant role {} to {}: {}", role, member.user.id, e),
+            }
+        }
+
+        for role in current.difference(&earned) {
+            match member.remove_role(http, *role).await {
+                Ok(_) => report.revoked += 1,
+                Err(e) => warn!("Unable to revoke role {} from {}: {}", role, member.user.id, e),
+            }
+        }
+
+        current_progress.checked += 1;
+        if let Some(progress) = progress {
+            progress.send_replace(current_progress);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Pages through the whole member list of the guild, bots are left out.
+async fn fetch_members(http: &Http, guild_id: GuildId) -> Result<Vec<Member>, Error> {
+    let mut members = vec![];
+    let mut after = None;
+    loop {
+        let page = guild_id.members(http, Some(MEMBERS_PAGE_SIZE), after).await?;
+        let last_page = (page.len() as u64) < MEMBERS_PAGE_SIZE;
+        after = page.last().map(|m| m.user.id);
+        members.extend(page.into_iter().filter(|m| !m.user.bot));
+
+        if last_page || after.is_none() {
+            return Ok(members);
+        }
+    }
+}
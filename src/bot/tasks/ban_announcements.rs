@@ -0,0 +1,114 @@
+use std::{sync::Arc, time::Duration};
+
+use log::{error, info};
+use serenity::all::{CreateEmbed, CreateEmbedFooter, CreateMessage, Http};
+
+use crate::{bot::{commands::ban::format_ban_summary, utilities::{generate_random_colour, resolve_optional_user_name, resolve_user_name}}, config::Config, database::{PgDatabase, ServerBan, ServerUnban}, error::Error};
+
+// void_relay_state keys of the last announced rows
+static LAST_BAN_KEY: &str = "ban_announcements.last_ban_id";
+static LAST_UNBAN_KEY: &str = "ban_announcements.last_unban_id";
+static BATCH_SIZE: i64 = 50;
+
+pub fn spawn_worker(http: Arc<Http>, db: PgDatabase, config: Config) {
+    if config.ban_announcements().is_empty() {
+        info!("No ban announcement channels configured, ban feed is disabled.");
+        return;
+    }
+    if config.ban_announcements_interval() == 0 {
+        info!("Ban announcements interval is 0, ban feed is disabled.");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.ban_announcements_interval()));
+        loop {
+            interval.tick().await;
+            if let Err(e) = poll(&http, &db, &config).await {
+                error!("Error polling bans for announcements: {e}");
+            }
+        }
+    });
+}
+
+/// Announces bans and unbans added since the last poll, no matter if they came from Discord or in-game.
+pub async fn poll(http: &Http, db: &PgDatabase, config: &Config) -> Result<(), Error> {
+    let last_ban_id = match db.get_bot_state(LAST_BAN_KEY).await? {
+        Some(id) => id as i32,
+        None => start_from(LAST_BAN_KEY, db.get_max_ban_id().await?, db).await?,
+    };
+
+    for id in db.get_ban_ids_after(last_ban_id, BATCH_SIZE).await? {
+        // rows which can't be read are skipped, otherwise the feed would be stuck on them forever
+        match db.get_ban_by_id(id).await {
+            Ok(Some(ban)) => announce_ban(&ban, http, db, config).await,
+            Ok(None) => {},
+            Err(e) => error!("Skipping announcement of ban {}: {}", id, e),
+        }
+        db.set_bot_state(LAST_BAN_KEY, id as i64).await?;
+    }
+
+    let last_unban_id = match db.get_bot_state(LAST_UNBAN_KEY).await? {
+        Some(id) => id as i32,
+        None => start_from(LAST_UNBAN_KEY, db.get_max_unban_id().await?, db).await?,
+    };
+
+    for unban in db.get_unbans_after(last_unban_id, BATCH_SIZE).await? {
+        announce_unban(&unban, http, db, config).await;
+        db.set_bot_state(LAST_UNBAN_KEY, unban.unban_id as i64).await?;
+    }
+
+    Ok(())
+}
+
+// on the first run only rows added from now on are announced, not the whole history
+async fn start_from(key: &str, id: i32, db: &PgDatabase) -> Result<i32, Error> {
+    info!("Starting {} from {}", key, id);
+    db.set_bot_state(key, id as i64).await?;
+    Ok(id)
+}
+
+async fn announce_ban(ban: &ServerBan, http: &Http, db: &PgDatabase, config: &Config) {
+    let player = resolve_optional_user_name(db, ban.player_user_id.as_ref()).await;
+    let admin = resolve_optional_user_name(db, ban.banning_admin.as_ref()).await;
+    let unbanned_by = if let Some(id) = ban.unbanning_admin { Some(resolve_user_name(db, &id).await) } else { None };
+
+    for channel in config.ban_announcements() {
+        let embed = CreateEmbed::new()
+            .title(format!("🔨 `{}` has been banned", player))
+            .description(format_ban_summary(ban, &admin, None, unbanned_by.clone(), &channel.redact))
+            .color(generate_random_colour())
+            .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+        if let Err(e) = channel.channel_id.send_message(http, CreateMessage::new().embed(embed)).await {
+            error!("Error announcing ban {} in {}: {}", ban.server_ban_id, channel.channel_id, e);
+        }
+    }
+}
+
+async fn announce_unban(unban: &ServerUnban, http: &Http, db: &PgDatabase, config: &Config) {
+    let player = match db.get_ban_by_id(unban.ban_id).await {
+        Ok(Some(ban)) => resolve_optional_user_name(db, ban.player_user_id.as_ref()).await,
+        Ok(None) => "Unknown".to_string(),
+        Err(e) => {
+            error!("Error retrieving pardoned ban {}: {}", unban.ban_id, e);
+            "Unknown".to_string()
+        }
+    };
+    let admin = match unban.unbanning_admin {
+        Some(admin) => resolve_user_name(db, &admin).await,
+        None => "Unknown".to_string(),
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("🕊️ Ban `{}` of `{}` has been pardoned", unban.ban_id, player))
+        .description(format!("✍️ **Pardoned By:** {}\n📅 **Pardoned At:** {}", admin, unban.unban_time))
+        .color(generate_random_colour())
+        .footer(CreateEmbedFooter::new("VoidRelay By JerryImMouse"));
+
+    for channel in config.ban_announcements() {
+        if let Err(e) = channel.channel_id.send_message(http, CreateMessage::new().embed(embed.clone())).await {
+            error!("Error announcing unban {} in {}: {}", unban.unban_id, channel.channel_id, e);
+        }
+    }
+}
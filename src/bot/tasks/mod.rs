@@ -0,0 +1,3 @@
+pub mod achievements;
+pub mod ban_announcements;
+pub mod presence;
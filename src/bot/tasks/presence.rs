@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use log::{debug, error, info};
+use serenity::all::{ActivityData, Context, OnlineStatus};
+
+use crate::{api::ss14client::SS14ApiClient, bot::commands::status::format_presence, config::Config};
+
+pub fn spawn_worker(ctx: Context, config: Config) {
+    if config.status_interval() == 0 {
+        info!("Status interval is 0, bot presence updates are disabled.");
+        return;
+    }
+
+    let ss14_client = match SS14ApiClient::new(config.api_url(), config.server_token()) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating ss14 client, bot presence updates are disabled: {e}");
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.status_interval()));
+        loop {
+            interval.tick().await;
+            match ss14_client.status(&config.status_url()).await {
+                Ok(status) => ctx.set_presence(Some(ActivityData::custom(format_presence(&status))), OnlineStatus::Online),
+                Err(e) => {
+                    debug!("Error fetching server status for presence: {e}");
+                    ctx.set_presence(Some(ActivityData::custom("Server is offline")), OnlineStatus::Idle);
+                }
+            }
+        }
+    });
+}
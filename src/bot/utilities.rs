@@ -1,10 +1,12 @@
-use log::warn;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{error, warn};
 use rand::Rng;
 use serde::Deserialize;
-use serenity::all::Colour;
+use serenity::all::{Colour, UserId};
+use sqlx::types::ipnetwork::IpNetwork;
 use uuid::Uuid;
 
-use crate::{database::PgDatabase, error::Error};
+use crate::{api::{discord_client::DiscordApiClient, ss14client::SS14ApiClient}, config::Config, database::PgDatabase, error::Error};
 
 #[derive(Debug, Deserialize)]
 pub struct AuthServerResponse { 
@@ -19,6 +21,7 @@ pub struct AuthErrorResponse {
 }
 
 static AUTH_SERVER: &str = "https://auth.spacestation14.com";
+static DATE_FORMAT: &str = "%Y-%m-%d";
 
 pub async fn lookup_user_id_by_login(login: &str) -> Result<Uuid, Error> {
     let response = reqwest::get(&format!("{AUTH_SERVER}/api/query/name?name={login}")).await?;
@@ -60,6 +63,186 @@ pub async fn resolve_user_name(db: &PgDatabase, user_id: &Uuid) -> String {
     }
 }
 
+/// Same as [`resolve_user_name`] for nullable columns, `Unknown` if there is no user.
+pub async fn resolve_optional_user_name(db: &PgDatabase, user_id: Option<&Uuid>) -> String {
+    match user_id {
+        Some(user_id) => resolve_user_name(db, user_id).await,
+        None => "Unknown".to_string(),
+    }
+}
+
+/// Resolves the SS14 UUID and in-game name of the Discord user invoking an admin command.
+pub async fn resolve_admin(caller: UserId, discord_api: &DiscordApiClient, db: &PgDatabase) -> Result<(Uuid, String), String> {
+    let admin_uuid = match discord_api.uuid(&caller.get().to_string()).await {
+        Some(uuid) => uuid,
+        None => return Err("You're probably unauthorized to perform this action.".to_string()),
+    };
+
+    match db.get_login_by_uuid(&admin_uuid).await {
+        Ok(Some(login)) => Ok((admin_uuid, login)),
+        Ok(None) => Err("Unable to fetch admin name.".to_string()),
+        Err(err) => {
+            warn!("Failed to fetch user name for {}: {}", admin_uuid, err);
+            Err("Unable to fetch admin name.".to_string())
+        }
+    }
+}
+
+pub fn prepare_clients(config: &Config) -> Result<(SS14ApiClient, DiscordApiClient), String> {
+    let auth_client = DiscordApiClient::new(config.auth_url(), config.auth_token());
+    if let Err(e) = auth_client {
+        error!("Error creating auth client: {e}");
+        return Err("Unable to setup authorization client.".to_string());
+    }
+    let auth_client = auth_client.unwrap();
+    
+    let ss14_client = SS14ApiClient::new(config.api_url(), config.server_token());
+    if let Err(e) = ss14_client {
+        error!("Error creating ss14 client: {e}");
+        return Err("Unable to setup ss14 client.".to_string());
+    }
+    let ss14_client = ss14_client.unwrap();
+
+    Ok((ss14_client, auth_client))
+}
+
+static DURATION_UNITS: [(&str, i64); 6] = [
+    ("y", 525_600),
+    ("mo", 43_200),
+    ("w", 10_080),
+    ("d", 1_440),
+    ("h", 60),
+    ("m", 1),
+];
+
+/// Parses durations like `30m`, `3d`, `2w`, `1mo`, `1d12h` or `perm` into minutes, 0 means permanent.
+/// Bare numbers are treated as minutes, `0` is refused so permanent bans can't happen by a typo.
+pub fn parse_duration(input: &str) -> Result<i64, String> {
+    let input = input.trim().to_lowercase();
+    let invalid = || format!("Invalid duration `{}`, expected something like `30m`, `3d`, `2w`, `1mo` or `perm`.", input);
+
+    if matches!(input.as_str(), "perm" | "permanent") {
+        return Ok(0);
+    }
+    if input.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total: i64 = 0;
+    let mut rest = input.as_str();
+    while !rest.is_empty() {
+        let digits_len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits_len == 0 {
+            return Err(invalid());
+        }
+        let amount: i64 = rest[..digits_len].parse().map_err(|_| invalid())?;
+        rest = &rest[digits_len..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit = rest[..unit_len].trim();
+        rest = &rest[unit_len..];
+
+        let multiplier = match unit {
+            "" | "m" | "min" | "mins" => 1,
+            "h" | "hr" | "hrs" => 60,
+            "d" => 1_440,
+            "w" => 10_080,
+            "mo" => 43_200,
+            "y" => 525_600,
+            _ => return Err(invalid()),
+        };
+
+        total = amount.checked_mul(multiplier)
+            .and_then(|minutes| total.checked_add(minutes))
+            .ok_or_else(|| "Duration is too long, use `perm` instead.".to_string())?;
+    }
+
+    if total == 0 {
+        return Err("Duration must be positive, use `perm` for permanent bans.".to_string());
+    }
+    if total > i32::MAX as i64 {
+        return Err("Duration is too long, use `perm` instead.".to_string());
+    }
+
+    Ok(total)
+}
+
+/// Formats minutes back into `1w 2d 3h` form, 0 is permanent.
+pub fn format_duration(minutes: i64) -> String {
+    if minutes <= 0 {
+        return "permanent".to_string();
+    }
+
+    let mut rest = minutes;
+    let mut parts = vec![];
+    for (unit, unit_minutes) in DURATION_UNITS {
+        if rest >= unit_minutes {
+            parts.push(format!("{}{}", rest / unit_minutes, unit));
+            rest %= unit_minutes;
+        }
+    }
+
+    parts.join(" ")
+}
+
+/// Parses `YYYY-MM-DD` date.
+pub fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date.trim(), DATE_FORMAT)
+        .map_err(|_| format!("Invalid date `{}`, use `YYYY-MM-DD`.", date))
+}
+
+pub fn format_date(date: &NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+/// Midnight of `date` in UTC.
+pub fn day_start(date: NaiveDate) -> DateTime<Utc> {
+    DateTime::<Utc>::from_naive_utc_and_offset(date.and_hms_opt(0, 0, 0).unwrap(), Utc)
+}
+
+/// HWIDs are raw bytes, shown as hex.
+pub fn format_hwid(hwid: &[u8]) -> String {
+    hwid.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Parses single address or CIDR range, e.g. `10.0.0.1` or `10.0.0.0/24`.
+pub fn parse_network(network: &str) -> Result<IpNetwork, String> {
+    let network = network.trim();
+    let parsed = match network.parse::<IpNetwork>() {
+        Ok(parsed) => parsed,
+        Err(_) => return Err(format!("Invalid IP or CIDR range `{}`.", network)),
+    };
+
+    // 10.0.0.1/24 is most likely a typo of 10.0.0.0/24
+    if parsed.ip() != parsed.network() {
+        return Err(format!("`{}` has host bits set, did you mean `{}/{}`?", network, parsed.network(), parsed.prefix()));
+    }
+
+    Ok(parsed)
+}
+
+/// Single address is shown without prefix length.
+pub fn format_network(network: &IpNetwork) -> String {
+    let max_prefix = match network {
+        IpNetwork::V4(_) => 32,
+        IpNetwork::V6(_) => 128,
+    };
+
+    if network.prefix() == max_prefix {
+        network.ip().to_string()
+    } else {
+        network.to_string()
+    }
+}
+
+/// Cuts message to `max_symbols` chars, adding `...` if it was longer.
+pub fn shorten_message(message: &str, max_symbols: usize) -> String {
+    match message.char_indices().nth(max_symbols) {
+        Some((idx, _)) => format!("{}...", &message[..idx]),
+        None => message.to_string(),
+    }
+}
+
 pub fn generate_random_colour() -> Colour {
     let mut rng_thread = rand::thread_rng();
     let r = rng_thread.gen::<u8>();
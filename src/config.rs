@@ -1,17 +1,118 @@
-use std::{fs, path::PathBuf, str::FromStr};
+use std::{collections::HashMap, fs, path::PathBuf, str::FromStr};
 
 use serde::Deserialize;
+use serenity::all::{ChannelId, RoleId};
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct Config {
     discord_bot_token: String,
     ss14_database: String,
     api_host: String,
+    // API server is disabled without it
+    #[serde(default)]
+    api_token: Option<String>,
     guild_id: String,
     ss14_server_token: String,
     ss14_api_url: String,
+    // public status endpoint of the game server, `/status` at the origin of `ss14_api_url` if missing
+    #[serde(default)]
+    ss14_status_url: Option<String>,
     authorization_url: String,
     authorization_token: String,
+    // "command.subcommand" -> required SS14 admin flag, null disables the check
+    #[serde(default)]
+    permissions: HashMap<String, Option<String>>,
+    // admin rank name -> longest ban duration it may issue, e.g. "1w"
+    #[serde(default)]
+    max_ban_durations: HashMap<String, String>,
+    #[serde(default)]
+    ban_templates: Vec<BanTemplate>,
+    #[serde(default = "default_confirmation_timeout")]
+    confirmation_timeout_secs: u64,
+    #[serde(default)]
+    achievements: Vec<AchievementRule>,
+    #[serde(default = "default_achievements_interval")]
+    achievements_interval_secs: u64,
+    // channel every moderation action taken through the bot is posted to
+    #[serde(default)]
+    audit_channel: Option<ChannelId>,
+    #[serde(default)]
+    ban_announcements: Vec<AnnouncementChannel>,
+    #[serde(default = "default_ban_announcements_interval")]
+    ban_announcements_interval_secs: u64,
+    // channel ahelp threads are created in, relay is disabled without it
+    #[serde(default)]
+    ahelp_channel: Option<ChannelId>,
+    #[serde(default = "default_status_interval")]
+    status_interval_secs: u64,
+}
+
+/// Grants `role_id` to players who spent at least `hours` on `tracker`, e.g. `JobCaptain` or `Overall`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AchievementRule {
+    pub tracker: String,
+    pub hours: u32,
+    pub role_id: RoleId,
+}
+
+/// Named ban reason with defaults, picked in `/bans ban` and editable in its modal.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BanTemplate {
+    pub name: String,
+    pub reason: String,
+    pub duration: String,
+    pub severity: u16,
+}
+
+/// Public channel new bans and unbans are announced to.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnnouncementChannel {
+    pub channel_id: ChannelId,
+    #[serde(default)]
+    pub redact: Redaction,
+}
+
+/// Ban details that shouldn't be shown in a channel.
+/// Everything is hidden by default, a channel has to opt in to exposing it.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Redaction {
+    #[serde(default = "default_redact")]
+    pub address: bool,
+    #[serde(default = "default_redact")]
+    pub hwid: bool,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self { address: default_redact(), hwid: default_redact() }
+    }
+}
+
+impl Redaction {
+    /// Shows everything, for admin-only responses.
+    pub fn none() -> Self {
+        Self { address: false, hwid: false }
+    }
+}
+
+fn default_redact() -> bool {
+    true
+}
+
+fn default_confirmation_timeout() -> u64 {
+    120
+}
+
+fn default_ban_announcements_interval() -> u64 {
+    60
+}
+
+fn default_status_interval() -> u64 {
+    60
+}
+
+fn default_achievements_interval() -> u64 {
+    3600
 }
 
 impl Config {
@@ -27,6 +128,10 @@ impl Config {
         &self.api_host
     }
 
+    pub fn host_token(&self) -> Option<&str> {
+        self.api_token.as_deref()
+    }
+
     pub fn guild(&self) -> &str {
         &self.guild_id
     }
@@ -46,6 +151,65 @@ impl Config {
     pub fn api_url(&self) -> &str {
         &self.ss14_api_url
     }
+
+    pub fn status_url(&self) -> String {
+        match &self.ss14_status_url {
+            Some(url) => url.clone(),
+            // status lives at the server root, not under admin api path, e.g. `http://host:1212/admins` -> `http://host:1212/status`
+            None => match reqwest::Url::parse(&self.ss14_api_url).and_then(|url| url.join("/status")) {
+                Ok(url) => url.to_string(),
+                Err(_) => format!("{}/status", self.ss14_api_url.trim_end_matches('/')),
+            },
+        }
+    }
+
+    pub fn status_interval(&self) -> u64 {
+        self.status_interval_secs
+    }
+
+    pub fn max_ban_duration(&self, rank: &str) -> Option<&str> {
+        self.max_ban_durations.get(rank).map(|d| d.as_str())
+    }
+
+    pub fn ban_templates(&self) -> &[BanTemplate] {
+        &self.ban_templates
+    }
+
+    pub fn ban_template(&self, name: &str) -> Option<&BanTemplate> {
+        self.ban_templates.iter().find(|t| t.name == name)
+    }
+
+    pub fn confirmation_timeout(&self) -> u64 {
+        self.confirmation_timeout_secs
+    }
+
+    pub fn achievements(&self) -> &[AchievementRule] {
+        &self.achievements
+    }
+
+    pub fn achievements_interval(&self) -> u64 {
+        self.achievements_interval_secs
+    }
+
+    pub fn audit_channel(&self) -> Option<ChannelId> {
+        self.audit_channel
+    }
+
+    pub fn ban_announcements(&self) -> &[AnnouncementChannel] {
+        &self.ban_announcements
+    }
+
+    pub fn ban_announcements_interval(&self) -> u64 {
+        self.ban_announcements_interval_secs
+    }
+
+    pub fn ahelp_channel(&self) -> Option<ChannelId> {
+        self.ahelp_channel
+    }
+
+    pub fn permissions(&self) -> &HashMap<String, Option<String>> {
+        &self.permissions
+    }
 }
 
 // assume str as path
@@ -1,10 +1,13 @@
 use chrono::{DateTime, Utc};
-use sqlx::{Executor, PgPool};
+use serde::Serialize;
+use sqlx::{Executor, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 use sqlx::Row;
+use sqlx::types::ipnetwork::IpNetwork;
 
 use crate::error::Error;
 
+#[derive(Clone)]
 pub struct PgDatabase {
     inner_pool: PgPool
 }
@@ -31,6 +34,22 @@ impl PgDatabase {
         Ok(Some(uuid))
     }
 
+    pub async fn search_logins(&self, prefix: &str, limit: i64) -> Result<Vec<String>, Error> {
+        // escape LIKE wildcards, logins may contain underscores
+        let pattern = format!("{}%", prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        let rows = sqlx::query(
+            "SELECT last_seen_user_name FROM player WHERE last_seen_user_name ILIKE $1 ESCAPE '\\' ORDER BY last_seen_user_name LIMIT $2"
+        ).bind(pattern)
+        .bind(limit)
+        .fetch_all(&self.inner_pool).await?;
+
+        let logins = rows.iter()
+            .map(|row| row.try_get::<String, _>("last_seen_user_name"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(logins)
+    }
+
     pub async fn whitelistadd(&self, user_id: &Uuid) -> Result<u64, Error> {
         let query = sqlx::query("INSERT INTO whitelist (user_id) VALUES ($1)");
         let query = query.bind(user_id);
@@ -63,32 +82,147 @@ impl PgDatabase {
     }
 
     pub async fn get_notes_list(&self, uuid: &Uuid) -> Result<Vec<AdminNoteShort>, Error> {
-        let notes = sqlx::query_as::<_, AdminNoteShort>("SELECT admin_notes_id, message FROM admin_notes WHERE player_user_id = $1")
+        let notes = sqlx::query_as::<_, AdminNoteShort>("SELECT admin_notes_id, message FROM admin_notes WHERE player_user_id = $1 AND deleted = FALSE")
             .bind(uuid)
             .fetch_all(&self.inner_pool).await?;
         
         Ok(notes)
     }
 
+    pub async fn get_notes_page(&self, uuid: &Uuid, page: i64, per_page: i64) -> Result<Vec<AdminNoteShort>, Error> {
+        let notes = sqlx::query_as::<_, AdminNoteShort>(
+            "SELECT admin_notes_id, message FROM admin_notes WHERE player_user_id = $1 AND deleted = FALSE ORDER BY admin_notes_id DESC LIMIT $2 OFFSET $3"
+        ).bind(uuid)
+        .bind(per_page)
+        .bind(page * per_page)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(notes)
+    }
+
+    pub async fn count_notes(&self, uuid: &Uuid) -> Result<i64, Error> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM admin_notes WHERE player_user_id = $1 AND deleted = FALSE")
+            .bind(uuid)
+            .fetch_one(&self.inner_pool).await?;
+
+        let count: i64 = row.try_get("count")?;
+        Ok(count)
+    }
+
     pub async fn get_bans_list(&self, uuid: &Uuid) -> Result<Vec<ServerBanShort>, Error> {
         let bans = sqlx::query_as::<_, ServerBanShort>(
-            "SELECT server_ban_id, reason FROM server_ban WHERE player_user_id = $1"
+            "SELECT b.server_ban_id, b.reason, b.expiration_time, u.unban_id IS NOT NULL AS pardoned
+            FROM server_ban b LEFT JOIN server_unban u ON u.ban_id = b.server_ban_id
+            WHERE b.player_user_id = $1
+            ORDER BY (u.unban_id IS NULL AND (b.expiration_time IS NULL OR b.expiration_time > NOW())) DESC, b.server_ban_id DESC"
         ).bind(uuid)
         .fetch_all(&self.inner_pool).await?;
 
+        Ok(bans)
+    }
+
+    pub async fn get_bans_page(&self, uuid: &Uuid, page: i64, per_page: i64) -> Result<Vec<ServerBanShort>, Error> {
+        let bans = sqlx::query_as::<_, ServerBanShort>(
+            "SELECT b.server_ban_id, b.reason, b.expiration_time, u.unban_id IS NOT NULL AS pardoned
+            FROM server_ban b LEFT JOIN server_unban u ON u.ban_id = b.server_ban_id
+            WHERE b.player_user_id = $1
+            ORDER BY (u.unban_id IS NULL AND (b.expiration_time IS NULL OR b.expiration_time > NOW())) DESC, b.server_ban_id DESC
+            LIMIT $2 OFFSET $3"
+        ).bind(uuid)
+        .bind(per_page)
+        .bind(page * per_page)
+        .fetch_all(&self.inner_pool).await?;
 
         Ok(bans)
     }
 
+    pub async fn count_bans(&self, uuid: &Uuid) -> Result<i64, Error> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM server_ban WHERE player_user_id = $1")
+            .bind(uuid)
+            .fetch_one(&self.inner_pool).await?;
+
+        let count: i64 = row.try_get("count")?;
+        Ok(count)
+    }
+
     pub async fn get_ban_by_id(&self, ban_id: i32) -> Result<Option<ServerBan>, Error> {
         let ban = sqlx::query_as::<_, ServerBan>(
-            "SELECT * FROM server_ban WHERE server_ban_id = $1"
+            "SELECT b.*, u.unbanning_admin, u.unban_time FROM server_ban b
+            LEFT JOIN server_unban u ON u.ban_id = b.server_ban_id
+            WHERE b.server_ban_id = $1"
         ).bind(ban_id)
         .fetch_optional(&self.inner_pool).await?;
 
         Ok(ban)
     }
 
+    pub async fn search_bans_page(&self, filter: &BanFilter, page: i64, per_page: i64) -> Result<Vec<BanSearchResult>, Error> {
+        let mut query = ban_search_query(
+            "SELECT b.server_ban_id, b.player_user_id, p.last_seen_user_name AS user_name, b.address, b.ban_time, b.expiration_time, b.reason,
+                EXISTS(SELECT 1 FROM server_unban WHERE ban_id = b.server_ban_id) AS pardoned
+            FROM server_ban b LEFT JOIN player p ON p.user_id = b.player_user_id",
+            filter,
+        );
+        query.push(" ORDER BY b.server_ban_id DESC LIMIT ").push_bind(per_page)
+            .push(" OFFSET ").push_bind(page * per_page);
+
+        let bans = query.build_query_as::<BanSearchResult>()
+            .fetch_all(&self.inner_pool).await?;
+
+        Ok(bans)
+    }
+
+    pub async fn count_bans_search(&self, filter: &BanFilter) -> Result<i64, Error> {
+        let mut query = ban_search_query("SELECT COUNT(*) AS count FROM server_ban b", filter);
+        let row = query.build().fetch_one(&self.inner_pool).await?;
+
+        let count: i64 = row.try_get("count")?;
+        Ok(count)
+    }
+
+    /// Players who were last seen or have ever connected from inside `network`.
+    pub async fn get_players_in_network(&self, network: &IpNetwork, limit: i64) -> Result<Vec<NetworkPlayer>, Error> {
+        let players = sqlx::query_as::<_, NetworkPlayer>(
+            "SELECT p.user_id, p.last_seen_user_name, p.last_seen_time, p.last_seen_address FROM player p
+            WHERE p.last_seen_address <<= $1
+            OR EXISTS (SELECT 1 FROM connection_log c WHERE c.user_id = p.user_id AND c.address <<= $1)
+            ORDER BY p.last_seen_time DESC LIMIT $2"
+        ).bind(network)
+        .bind(limit)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(players)
+    }
+
+    pub async fn edit_ban(&self, ban_id: i32, edit: ServerBanEdit) -> Result<u64, Error> {
+        let query = sqlx::query(
+            r#"UPDATE server_ban SET
+                reason = COALESCE($2, reason),
+                expiration_time = CASE WHEN $3 THEN $4 ELSE expiration_time END,
+                last_edited_by_id = $5,
+                last_edited_at = NOW()
+            WHERE server_ban_id = $1"#
+        ).bind(ban_id)
+        .bind(edit.reason)
+        .bind(edit.expiration_time.is_some())
+        .bind(edit.expiration_time.flatten())
+        .bind(edit.edited_by);
+
+        let affected_rows = self.inner_pool.execute(query).await?.rows_affected();
+        Ok(affected_rows)
+    }
+
+    /// Marks ban as edited by `edited_by` without changing it, for edits made by the game server.
+    pub async fn set_ban_last_edited(&self, ban_id: i32, edited_by: &Uuid) -> Result<u64, Error> {
+        let query = sqlx::query(
+            "UPDATE server_ban SET last_edited_by_id = $2, last_edited_at = NOW() WHERE server_ban_id = $1"
+        ).bind(ban_id)
+        .bind(edited_by);
+
+        let affected_rows = self.inner_pool.execute(query).await?.rows_affected();
+        Ok(affected_rows)
+    }
+
     pub async fn get_note_by_id(&self, note_id: i32) -> Result<Option<AdminNote>, Error> {
         let note = sqlx::query_as::<_, AdminNote>(
             "SELECT * FROM admin_notes WHERE admin_notes_id = $1"
@@ -98,22 +232,483 @@ impl PgDatabase {
         Ok(note)
     }
 
+    pub async fn add_admin_note(&self, note: NewAdminNote, created_by: &Uuid) -> Result<i32, Error> {
+        // playtime and round are filled the same way the game does it: overall playtime and current round
+        let row = sqlx::query(
+            r#"INSERT INTO admin_notes (
+                round_id, player_user_id, playtime_at_note, message, severity, secret, expiration_time,
+                created_by_id, created_at, last_edited_by_id, last_edited_at, deleted
+            ) VALUES (
+                (SELECT MAX(round_id) FROM round), $1,
+                COALESCE((SELECT time_spent FROM play_time WHERE player_id = $1 AND tracker = 'Overall'), INTERVAL '0'),
+                $2, $3, $4, $5, $6, NOW(), $6, NOW(), FALSE
+            ) RETURNING admin_notes_id"#
+        ).bind(note.player_user_id)
+        .bind(note.message)
+        .bind(note.severity)
+        .bind(note.secret)
+        .bind(note.expiration_time)
+        .bind(created_by)
+        .fetch_one(&self.inner_pool).await?;
+
+        let id: i32 = row.try_get("admin_notes_id")?;
+        Ok(id)
+    }
+
+    pub async fn edit_admin_note(&self, note_id: i32, edit: AdminNoteEdit, edited_by: &Uuid) -> Result<u64, Error> {
+        let query = sqlx::query(
+            r#"UPDATE admin_notes SET
+                message = COALESCE($2, message),
+                severity = COALESCE($3, severity),
+                secret = COALESCE($4, secret),
+                expiration_time = CASE WHEN $5 THEN $6 ELSE expiration_time END,
+                last_edited_by_id = $7,
+                last_edited_at = NOW()
+            WHERE admin_notes_id = $1 AND deleted = FALSE"#
+        ).bind(note_id)
+        .bind(edit.message)
+        .bind(edit.severity)
+        .bind(edit.secret)
+        .bind(edit.expiration_time.is_some())
+        .bind(edit.expiration_time.flatten())
+        .bind(edited_by);
+
+        let affected_rows = self.inner_pool.execute(query).await?.rows_affected();
+        Ok(affected_rows)
+    }
+
+    pub async fn delete_admin_note(&self, note_id: i32, deleted_by: &Uuid) -> Result<u64, Error> {
+        let query = sqlx::query(
+            "UPDATE admin_notes SET deleted = TRUE, deleted_by_id = $2, deleted_at = NOW() WHERE admin_notes_id = $1 AND deleted = FALSE"
+        ).bind(note_id)
+        .bind(deleted_by);
+
+        let affected_rows = self.inner_pool.execute(query).await?.rows_affected();
+        Ok(affected_rows)
+    }
+
+    pub async fn get_role_bans_page(&self, uuid: &Uuid, page: i64, per_page: i64) -> Result<Vec<ServerRoleBanShort>, Error> {
+        let bans = sqlx::query_as::<_, ServerRoleBanShort>(
+            "SELECT server_role_ban_id, role_id, reason FROM server_role_ban WHERE player_user_id = $1
+            ORDER BY server_role_ban_id DESC LIMIT $2 OFFSET $3"
+        ).bind(uuid)
+        .bind(per_page)
+        .bind(page * per_page)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(bans)
+    }
+
+    pub async fn count_role_bans(&self, uuid: &Uuid) -> Result<i64, Error> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM server_role_ban WHERE player_user_id = $1")
+            .bind(uuid)
+            .fetch_one(&self.inner_pool).await?;
+
+        let count: i64 = row.try_get("count")?;
+        Ok(count)
+    }
+
+    pub async fn get_role_ban_by_id(&self, ban_id: i32) -> Result<Option<ServerRoleBan>, Error> {
+        let ban = sqlx::query_as::<_, ServerRoleBan>(
+            r#"SELECT b.server_role_ban_id, b.player_user_id, b.address, b.ban_time, b.expiration_time, b.reason,
+                b.banning_admin, b.role_id, b.severity, b.round_id, b.last_edited_at, b.last_edited_by_id,
+                u.unbanning_admin, u.unban_time
+            FROM server_role_ban b LEFT JOIN server_role_unban u ON u.ban_id = b.server_role_ban_id
+            WHERE b.server_role_ban_id = $1"#
+        ).bind(ban_id)
+        .fetch_optional(&self.inner_pool).await?;
+
+        Ok(ban)
+    }
+
+    pub async fn add_role_ban(&self, ban: NewServerRoleBan) -> Result<i32, Error> {
+        let row = sqlx::query(
+            r#"INSERT INTO server_role_ban (
+                player_user_id, ban_time, expiration_time, reason, banning_admin, role_id, severity, round_id, playtime_at_note
+            ) VALUES (
+                $1, NOW(), $2, $3, $4, $5, $6, (SELECT MAX(round_id) FROM round),
+                COALESCE((SELECT time_spent FROM play_time WHERE player_id = $1 AND tracker = 'Overall'), INTERVAL '0')
+            ) RETURNING server_role_ban_id"#
+        ).bind(ban.player_user_id)
+        .bind(ban.expiration_time)
+        .bind(ban.reason)
+        .bind(ban.banning_admin)
+        .bind(ban.role_id)
+        .bind(ban.severity)
+        .fetch_one(&self.inner_pool).await?;
+
+        let id: i32 = row.try_get("server_role_ban_id")?;
+        Ok(id)
+    }
+
+    pub async fn add_role_unban(&self, ban_id: i32, unbanning_admin: &Uuid) -> Result<u64, Error> {
+        let query = sqlx::query(
+            r#"INSERT INTO server_role_unban (ban_id, unbanning_admin, unban_time)
+            SELECT server_role_ban_id, $2, NOW() FROM server_role_ban
+            WHERE server_role_ban_id = $1 AND NOT EXISTS (SELECT 1 FROM server_role_unban WHERE ban_id = $1)"#
+        ).bind(ban_id)
+        .bind(unbanning_admin);
+
+        let affected_rows = self.inner_pool.execute(query).await?.rows_affected();
+        Ok(affected_rows)
+    }
+
+    pub async fn get_player_profile(&self, uuid: &Uuid) -> Result<Option<PlayerProfile>, Error> {
+        let profile = sqlx::query_as::<_, PlayerProfile>(
+            "SELECT p.user_id, p.last_seen_user_name, p.first_seen_time, p.last_seen_time, p.last_seen_address, p.last_seen_hwid,
+                (SELECT COUNT(*) FROM server_ban WHERE player_user_id = p.user_id) AS bans,
+                (SELECT COUNT(*) FROM server_role_ban WHERE player_user_id = p.user_id) AS role_bans,
+                (SELECT COUNT(*) FROM admin_notes WHERE player_user_id = p.user_id AND deleted = FALSE) AS notes,
+                COALESCE((SELECT EXTRACT(EPOCH FROM time_spent)::BIGINT FROM play_time WHERE player_id = p.user_id AND tracker = 'Overall'), 0) AS playtime_seconds,
+                EXISTS(SELECT 1 FROM whitelist WHERE user_id = p.user_id) AS whitelisted
+            FROM player p WHERE p.user_id = $1"
+        ).bind(uuid)
+        .fetch_optional(&self.inner_pool).await?;
+
+        Ok(profile)
+    }
+
+    pub async fn get_connections_page(&self, filter: &ConnectionFilter, page: i64, per_page: i64) -> Result<Vec<ConnectionLog>, Error> {
+        let connections = sqlx::query_as::<_, ConnectionLog>(
+            "SELECT c.connection_log_id, c.user_name, c.time, c.address, c.hwid, c.denied, COALESCE(s.name, '') AS server_name
+            FROM connection_log c LEFT JOIN server s ON s.server_id = c.server_id
+            WHERE c.user_id = $1
+            AND ($2::TIMESTAMPTZ IS NULL OR c.time >= $2)
+            AND ($3::TIMESTAMPTZ IS NULL OR c.time < $3)
+            AND (NOT $4 OR c.denied IS NOT NULL)
+            ORDER BY c.time DESC LIMIT $5 OFFSET $6"
+        ).bind(filter.user_id)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.denied_only)
+        .bind(per_page)
+        .bind(page * per_page)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(connections)
+    }
+
+    pub async fn count_connections(&self, filter: &ConnectionFilter) -> Result<i64, Error> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS count FROM connection_log
+            WHERE user_id = $1
+            AND ($2::TIMESTAMPTZ IS NULL OR time >= $2)
+            AND ($3::TIMESTAMPTZ IS NULL OR time < $3)
+            AND (NOT $4 OR denied IS NOT NULL)"
+        ).bind(filter.user_id)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.denied_only)
+        .fetch_one(&self.inner_pool).await?;
+
+        let count: i64 = row.try_get("count")?;
+        Ok(count)
+    }
+
+    /// Accounts which connected from any address or HWID the player has ever used.
+    /// Both `connection_log` and `player` last seen data are taken into account.
+    pub async fn get_alt_accounts(&self, uuid: &Uuid, limit: i64) -> Result<Vec<AltAccount>, Error> {
+        let alts = sqlx::query_as::<_, AltAccount>(
+            "WITH target AS (
+                SELECT address, hwid FROM connection_log WHERE user_id = $1
+                UNION SELECT last_seen_address, last_seen_hwid FROM player WHERE user_id = $1
+            ),
+            -- only rows overlapping with target, the rest of the log is never aggregated
+            entries AS (
+                SELECT user_id, address, hwid, time FROM connection_log
+                WHERE user_id <> $1 AND (address IN (SELECT address FROM target) OR hwid IN (SELECT hwid FROM target WHERE LENGTH(hwid) > 0))
+                UNION ALL SELECT user_id, last_seen_address, last_seen_hwid, last_seen_time FROM player
+                WHERE user_id <> $1 AND (last_seen_address IN (SELECT address FROM target) OR last_seen_hwid IN (SELECT hwid FROM target WHERE LENGTH(hwid) > 0))
+            ),
+            matches AS (
+                SELECT user_id, time,
+                    address IN (SELECT address FROM target) AS shared_address,
+                    COALESCE(LENGTH(hwid) > 0 AND hwid IN (SELECT hwid FROM target WHERE hwid IS NOT NULL), FALSE) AS shared_hwid
+                FROM entries
+            )
+            SELECT m.user_id, p.last_seen_user_name AS user_name,
+                COUNT(*) FILTER (WHERE m.shared_address) AS address_overlaps,
+                COUNT(*) FILTER (WHERE m.shared_hwid) AS hwid_overlaps,
+                MIN(m.time) AS first_overlap,
+                MAX(m.time) AS last_overlap,
+                EXISTS(
+                    SELECT 1 FROM server_ban b WHERE b.player_user_id = m.user_id
+                    AND (b.expiration_time IS NULL OR b.expiration_time > NOW())
+                    AND NOT EXISTS (SELECT 1 FROM server_unban WHERE ban_id = b.server_ban_id)
+                ) AS banned
+            FROM matches m JOIN player p ON p.user_id = m.user_id
+            WHERE m.shared_address OR m.shared_hwid
+            GROUP BY m.user_id, p.last_seen_user_name
+            ORDER BY COUNT(*) DESC, MAX(m.time) DESC LIMIT $2"
+        ).bind(uuid)
+        .bind(limit)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(alts)
+    }
+
+    pub async fn get_play_times(&self, trackers: &[String]) -> Result<Vec<PlayTime>, Error> {
+        let play_times = sqlx::query_as::<_, PlayTime>(
+            "SELECT player_id, tracker, EXTRACT(EPOCH FROM time_spent)::BIGINT AS seconds FROM play_time WHERE tracker = ANY($1)"
+        ).bind(trackers)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(play_times)
+    }
+
+    pub async fn get_admin(&self, uuid: &Uuid) -> Result<Option<Admin>, Error> {
+        let admin = sqlx::query_as::<_, Admin>(
+            "SELECT a.user_id, a.title, a.admin_rank_id, a.suspended, a.deadminned, r.name AS rank_name FROM admin a LEFT JOIN admin_rank r ON r.admin_rank_id = a.admin_rank_id WHERE a.user_id = $1"
+        ).bind(uuid)
+        .fetch_optional(&self.inner_pool).await?;
+
+        Ok(admin)
+    }
+
+    pub async fn get_admin_flags(&self, uuid: &Uuid) -> Result<Vec<AdminFlag>, Error> {
+        let flags = sqlx::query_as::<_, AdminFlag>(
+            "SELECT flag, negative FROM admin_flag WHERE admin_id = $1"
+        ).bind(uuid)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(flags)
+    }
+
+    pub async fn get_admin_rank_flags(&self, rank_id: i32) -> Result<Vec<String>, Error> {
+        let rows = sqlx::query("SELECT flag FROM admin_rank_flag WHERE admin_rank_id = $1")
+            .bind(rank_id)
+            .fetch_all(&self.inner_pool).await?;
+
+        let flags = rows.iter()
+            .map(|row| row.try_get::<String, _>("flag"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(flags)
+    }
+
+    /// Creates tables owned by the bot itself, SS14 migrations know nothing about them.
+    pub async fn create_bot_tables(&self) -> Result<(), Error> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS void_relay_audit (
+                void_relay_audit_id SERIAL PRIMARY KEY,
+                time TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                actor_discord_id BIGINT, -- NULL for HTTP API calls
+                actor_user_id UUID,
+                actor_name TEXT NOT NULL,
+                action TEXT NOT NULL,
+                target TEXT NOT NULL,
+                parameters JSONB NOT NULL DEFAULT '{}'
+            )"
+        ).execute(&self.inner_pool).await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS void_relay_audit_time_idx ON void_relay_audit (time DESC)")
+            .execute(&self.inner_pool).await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS void_relay_ahelp_thread (
+                player_user_id UUID PRIMARY KEY,
+                thread_id BIGINT NOT NULL UNIQUE
+            )"
+        ).execute(&self.inner_pool).await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS void_relay_state (
+                key TEXT PRIMARY KEY,
+                value BIGINT NOT NULL
+            )"
+        ).execute(&self.inner_pool).await?;
+
+        Ok(())
+    }
+
+    pub async fn add_audit_record(&self, record: NewAuditRecord) -> Result<i32, Error> {
+        let row = sqlx::query(
+            "INSERT INTO void_relay_audit (actor_discord_id, actor_user_id, actor_name, action, target, parameters)
+            VALUES ($1, $2, $3, $4, $5, $6::jsonb)
+            RETURNING void_relay_audit_id"
+        ).bind(record.actor_discord_id)
+        .bind(record.actor_user_id)
+        .bind(record.actor_name)
+        .bind(record.action)
+        .bind(record.target)
+        .bind(record.parameters)
+        .fetch_one(&self.inner_pool).await?;
+
+        Ok(row.try_get("void_relay_audit_id")?)
+    }
+
+    /// Newest audit records first, `None` filters match everything.
+    pub async fn search_audit(&self, filter: &AuditFilter, limit: i64) -> Result<Vec<AuditRecord>, Error> {
+        let target = filter.target.as_deref().map(contains_pattern);
+
+        let records = sqlx::query_as::<_, AuditRecord>(
+            "SELECT void_relay_audit_id, time, actor_discord_id, actor_user_id, actor_name, action, target, parameters::TEXT AS parameters
+            FROM void_relay_audit
+            WHERE ($1::BIGINT IS NULL OR actor_discord_id = $1)
+            AND ($2::TEXT IS NULL OR action = $2)
+            AND ($3::TEXT IS NULL OR target ILIKE $3 ESCAPE '\\')
+            ORDER BY time DESC LIMIT $4"
+        ).bind(filter.actor_discord_id)
+        .bind(filter.action.as_ref())
+        .bind(target)
+        .bind(limit)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(records)
+    }
+
+    pub async fn get_bot_state(&self, key: &str) -> Result<Option<i64>, Error> {
+        let row = sqlx::query("SELECT value FROM void_relay_state WHERE key = $1")
+            .bind(key)
+            .fetch_optional(&self.inner_pool).await?;
+
+        match row {
+            Some(row) => Ok(Some(row.try_get("value")?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_bot_state(&self, key: &str, value: i64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO void_relay_state (key, value) VALUES ($1, $2)
+            ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value"
+        ).bind(key)
+        .bind(value)
+        .execute(&self.inner_pool).await?;
+
+        Ok(())
+    }
+
+    pub async fn get_ahelp_thread(&self, player: &Uuid) -> Result<Option<i64>, Error> {
+        let row = sqlx::query("SELECT thread_id FROM void_relay_ahelp_thread WHERE player_user_id = $1")
+            .bind(player)
+            .fetch_optional(&self.inner_pool).await?;
+
+        match row {
+            Some(row) => Ok(Some(row.try_get("thread_id")?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_ahelp_player(&self, thread_id: i64) -> Result<Option<Uuid>, Error> {
+        let row = sqlx::query("SELECT player_user_id FROM void_relay_ahelp_thread WHERE thread_id = $1")
+            .bind(thread_id)
+            .fetch_optional(&self.inner_pool).await?;
+
+        match row {
+            Some(row) => Ok(Some(row.try_get("player_user_id")?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_ahelp_thread(&self, player: &Uuid, thread_id: i64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO void_relay_ahelp_thread (player_user_id, thread_id) VALUES ($1, $2)
+            ON CONFLICT (player_user_id) DO UPDATE SET thread_id = EXCLUDED.thread_id"
+        ).bind(player)
+        .bind(thread_id)
+        .execute(&self.inner_pool).await?;
+
+        Ok(())
+    }
+
+    pub async fn get_max_ban_id(&self) -> Result<i32, Error> {
+        let row = sqlx::query("SELECT COALESCE(MAX(server_ban_id), 0) AS max FROM server_ban")
+            .fetch_one(&self.inner_pool).await?;
+
+        Ok(row.try_get("max")?)
+    }
+
+    pub async fn get_max_unban_id(&self) -> Result<i32, Error> {
+        let row = sqlx::query("SELECT COALESCE(MAX(unban_id), 0) AS max FROM server_unban")
+            .fetch_one(&self.inner_pool).await?;
+
+        Ok(row.try_get("max")?)
+    }
+
+    /// Ids of bans with id greater than `last_id`, oldest first.
+    /// Only ids are fetched, so a single bad row can't fail the whole batch.
+    pub async fn get_ban_ids_after(&self, last_id: i32, limit: i64) -> Result<Vec<i32>, Error> {
+        let rows = sqlx::query("SELECT server_ban_id FROM server_ban WHERE server_ban_id > $1 ORDER BY server_ban_id LIMIT $2")
+            .bind(last_id)
+            .bind(limit)
+            .fetch_all(&self.inner_pool).await?;
+
+        let ids = rows.iter()
+            .map(|row| row.try_get("server_ban_id"))
+            .collect::<Result<Vec<i32>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Unbans with id greater than `last_id`, oldest first.
+    pub async fn get_unbans_after(&self, last_id: i32, limit: i64) -> Result<Vec<ServerUnban>, Error> {
+        let unbans = sqlx::query_as::<_, ServerUnban>(
+            "SELECT unban_id, ban_id, unbanning_admin, unban_time FROM server_unban WHERE unban_id > $1 ORDER BY unban_id LIMIT $2"
+        ).bind(last_id)
+        .bind(limit)
+        .fetch_all(&self.inner_pool).await?;
+
+        Ok(unbans)
+    }
+
     pub async fn close(&self) {
         self.inner_pool.close().await;
     }
 }
 
+/// Appends conditions of every set filter to `select`, server_ban must be aliased as `b`.
+/// Values are always bound, so any combination of filters stays parameterized.
+fn ban_search_query<'a>(select: &str, filter: &'a BanFilter) -> QueryBuilder<'a, Postgres> {
+    let mut query = QueryBuilder::new(select);
+    query.push(" WHERE TRUE");
+
+    if let Some(address) = &filter.address {
+        // overlap, so range bans covering the address are found too
+        query.push(" AND b.address && ").push_bind(address);
+    }
+    if let Some(admin) = &filter.banning_admin {
+        query.push(" AND b.banning_admin = ").push_bind(admin);
+    }
+    if let Some(from) = &filter.from {
+        query.push(" AND b.ban_time >= ").push_bind(from);
+    }
+    if let Some(to) = &filter.to {
+        query.push(" AND b.ban_time < ").push_bind(to);
+    }
+    if let Some(reason) = &filter.reason {
+        query.push(" AND b.reason ILIKE ").push_bind(contains_pattern(reason)).push(" ESCAPE '\\'");
+    }
+    if let Some(round_id) = &filter.round_id {
+        query.push(" AND b.round_id = ").push_bind(round_id);
+    }
+
+    let pardoned = "EXISTS (SELECT 1 FROM server_unban WHERE ban_id = b.server_ban_id)";
+    match filter.state {
+        Some(BanState::Active) => query.push(format!(" AND NOT {} AND (b.expiration_time IS NULL OR b.expiration_time > NOW())", pardoned)),
+        Some(BanState::Expired) => query.push(format!(" AND NOT {} AND b.expiration_time <= NOW()", pardoned)),
+        Some(BanState::Pardoned) => query.push(format!(" AND {}", pardoned)),
+        None => &mut query,
+    };
+
+    query
+}
+
+/// ILIKE pattern matching `text` anywhere, wildcards are escaped so it's matched literally.
+fn contains_pattern(text: &str) -> String {
+    format!("%{}%", text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"))
+}
+
 // data structs
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct AdminNoteShort {
     pub admin_notes_id: i32,
     pub message: String,
 }
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct AdminNote {
-    pub round_id: i32,
+    pub round_id: Option<i32>, // None if note was added before any round
     pub player_user_id: Uuid,
     pub message: String,
     pub created_by_id: Uuid,
@@ -128,24 +723,234 @@ pub struct AdminNote {
     pub severity: i32
 }
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone)]
+pub struct NewAdminNote {
+    pub player_user_id: Uuid,
+    pub message: String,
+    pub severity: i32,
+    pub secret: bool,
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+// None fields are left untouched, `Some(None)` expiration removes it
+#[derive(Debug, Clone, Default)]
+pub struct AdminNoteEdit {
+    pub message: Option<String>,
+    pub severity: Option<i32>,
+    pub secret: Option<bool>,
+    pub expiration_time: Option<Option<DateTime<Utc>>>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct ServerBanShort {
     pub server_ban_id: i32,
     pub reason: String,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub pardoned: bool,
 }
 
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct ServerBan {
     pub server_ban_id: i32,
-    pub player_user_id: Uuid,
-    pub address: std::net::IpAddr,
+    pub player_user_id: Option<Uuid>, // None for IP-only bans
+    pub address: Option<IpNetwork>,
     pub ban_time: DateTime<Utc>,
     pub expiration_time: Option<DateTime<Utc>>,
     pub reason: String,
-    pub banning_admin: Uuid,
-    pub hwid: Vec<u8>,
+    pub banning_admin: Option<Uuid>,
+    pub hwid: Option<Vec<u8>>,
     pub auto_delete: bool,
     pub last_edited_at: Option<DateTime<Utc>>,
     pub last_edited_by_id: Option<Uuid>,
     pub round_id: Option<i32>,
-}
\ No newline at end of file
+    // from server_unban, unban_time is None if ban is not pardoned
+    pub unbanning_admin: Option<Uuid>,
+    pub unban_time: Option<DateTime<Utc>>,
+}
+
+// None fields are left as they are
+#[derive(Debug, Clone, Default)]
+pub struct ServerBanEdit {
+    pub edited_by: Uuid,
+    pub reason: Option<String>,
+    pub expiration_time: Option<Option<DateTime<Utc>>>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct BanSearchResult {
+    pub server_ban_id: i32,
+    pub player_user_id: Option<Uuid>, // None for IP-only bans
+    pub user_name: Option<String>,
+    pub address: Option<IpNetwork>,
+    pub ban_time: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub reason: String,
+    pub pardoned: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BanState {
+    Active,
+    Expired,
+    Pardoned,
+}
+
+// every field is optional, `to` is exclusive
+#[derive(Debug, Clone, Default)]
+pub struct BanFilter {
+    pub address: Option<IpNetwork>,
+    pub banning_admin: Option<Uuid>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub state: Option<BanState>,
+    pub reason: Option<String>,
+    pub round_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct NetworkPlayer {
+    pub user_id: Uuid,
+    pub last_seen_user_name: String,
+    pub last_seen_time: DateTime<Utc>,
+    pub last_seen_address: std::net::IpAddr,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ServerUnban {
+    pub unban_id: i32,
+    pub ban_id: i32,
+    pub unbanning_admin: Option<Uuid>,
+    pub unban_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ServerRoleBanShort {
+    pub server_role_ban_id: i32,
+    pub role_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ServerRoleBan {
+    pub server_role_ban_id: i32,
+    pub player_user_id: Option<Uuid>,
+    pub address: Option<IpNetwork>,
+    pub ban_time: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub reason: String,
+    pub banning_admin: Option<Uuid>,
+    pub role_id: String,
+    pub severity: i32,
+    pub round_id: Option<i32>,
+    pub last_edited_at: Option<DateTime<Utc>>,
+    pub last_edited_by_id: Option<Uuid>,
+    pub unbanning_admin: Option<Uuid>,
+    pub unban_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewServerRoleBan {
+    pub player_user_id: Uuid,
+    pub banning_admin: Uuid,
+    pub role_id: String,
+    pub reason: String,
+    pub severity: i32,
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PlayerProfile {
+    pub user_id: Uuid,
+    pub last_seen_user_name: String,
+    pub first_seen_time: DateTime<Utc>,
+    pub last_seen_time: DateTime<Utc>,
+    pub last_seen_address: std::net::IpAddr,
+    pub last_seen_hwid: Option<Vec<u8>>,
+    pub bans: i64,
+    pub role_bans: i64,
+    pub notes: i64,
+    pub playtime_seconds: i64,
+    pub whitelisted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ConnectionLog {
+    pub connection_log_id: i32,
+    pub user_name: String,
+    pub time: DateTime<Utc>,
+    pub address: std::net::IpAddr,
+    pub hwid: Option<Vec<u8>>,
+    pub denied: Option<i32>, // ConnectionDenyReason, None if connection was allowed
+    pub server_name: String,
+}
+
+// `to` is exclusive
+#[derive(Debug, Clone)]
+pub struct ConnectionFilter {
+    pub user_id: Uuid,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub denied_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AltAccount {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub address_overlaps: i64,
+    pub hwid_overlaps: i64,
+    pub first_overlap: DateTime<Utc>,
+    pub last_overlap: DateTime<Utc>,
+    pub banned: bool, // has active ban
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PlayTime {
+    pub player_id: Uuid,
+    pub tracker: String,
+    pub seconds: i64,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Admin {
+    pub user_id: Uuid,
+    pub title: Option<String>,
+    pub admin_rank_id: Option<i32>,
+    pub suspended: bool,
+    pub deadminned: bool,
+    pub rank_name: Option<String>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AdminFlag {
+    pub flag: String,
+    pub negative: bool,
+}
+
+pub struct NewAuditRecord {
+    pub actor_discord_id: Option<i64>,
+    pub actor_user_id: Option<Uuid>,
+    pub actor_name: String,
+    pub action: String,
+    pub target: String,
+    pub parameters: String, // JSON object
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AuditRecord {
+    pub void_relay_audit_id: i32,
+    pub time: DateTime<Utc>,
+    pub actor_discord_id: Option<i64>,
+    pub actor_user_id: Option<Uuid>,
+    pub actor_name: String,
+    pub action: String,
+    pub target: String,
+    pub parameters: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor_discord_id: Option<i64>,
+    pub action: Option<String>,
+    pub target: Option<String>,
+}
@@ -11,15 +11,19 @@ pub enum Error {
     #[error("SQLx Error: {0}")]
     SqlxError(#[from] sqlx::Error),
     #[error("Serenity client error: {0}")]
-    SerenityErr(#[from] serenity::Error),
+    SerenityErr(Box<serenity::Error>),
     #[error("Reqwest error: {0}")]
     ReqwestErr(#[from] reqwest::Error),
     #[error("UUID Parse Error: {0}")]
     UuidErr(#[from] uuid::Error),
     #[error("SS14 Api Error: {0}")]
     SS14ApiError(String),
+    #[error("SS14 Api doesn't support action: {0}")]
+    SS14ApiUnsupported(String),
     #[error("TypeAuthD Api Error: {0}")]
     TypeAuthDApiError(String),
+    #[error("Config Error: {0}")]
+    ConfigErr(String),
 }
 
 impl From<ErrorResponse> for Error {
@@ -28,6 +32,12 @@ impl From<ErrorResponse> for Error {
     }
 }
 
+impl From<serenity::Error> for Error {
+    fn from(value: serenity::Error) -> Self {
+        Self::SerenityErr(Box::new(value))
+    }
+}
+
 impl Error {
     pub fn ss14_api(err: &str) -> Self {
         Self::SS14ApiError(err.to_string())
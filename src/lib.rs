@@ -1,15 +1,31 @@
 use bot::DiscordBot;
 use config::Config;
+use database::PgDatabase;
 use error::Error;
+use log::{error, info};
+use server::ApiServer;
 
 pub mod bot;
 pub mod api;
 pub mod config;
 pub mod error;
 pub mod database;
+pub mod server;
 
 pub async fn run(config: Config) -> Result<(), Error> {
-    let bot = DiscordBot::new(&config)?;
+    let db = PgDatabase::new(config.cstr())?;
+    info!("Connected to SS14 database.");
+
+    db.create_bot_tables().await?;
+
+    let server = ApiServer::new(&config, db.clone());
+    tokio::spawn(async move {
+        if let Err(e) = server.start().await {
+            error!("API server error: {e}");
+        }
+    });
+
+    let bot = DiscordBot::new(&config, db);
 
     bot.start().await;
     Ok(())
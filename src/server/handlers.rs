@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{api::ss14client::{BanRequest, PardonRequest, SS14ApiActor, SS14ApiClient}, bot::{ahelp::{relay_to_discord, AhelpMessage}, audit::{self, AuditEntry}, permissions::{check_duration_limit, load_permissions, required_flag}, utilities::{format_duration, get_user_id_by_login, parse_duration, resolve_optional_user_name}}, database::{AdminNote, AdminNoteShort, ServerBan, ServerBanShort}, error::Error};
+
+use super::{ApiError, ApiState};
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+// requests
+
+#[derive(Debug, Deserialize)]
+pub struct LoginQuery {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BanBody {
+    admin: Uuid,
+    login: String,
+    duration: String, // same as slash command, e.g. `3d` or `perm`
+    reason: Option<String>,
+    severity: Option<u16>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PardonBody {
+    admin: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WhitelistBody {
+    admin: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AhelpBody {
+    user_id: Uuid,
+    username: String,
+    sender: Option<String>, // player themselves if missing
+    message: String,
+}
+
+// responses
+
+#[derive(Debug, Serialize)]
+pub struct AhelpResponse {
+    thread_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusResponse {
+    message: String,
+}
+
+impl StatusResponse {
+    fn new(message: String) -> Json<Self> {
+        Json(Self { message })
+    }
+}
+
+async fn resolve_login(login: &str, state: &ApiState) -> Result<Uuid, ApiError> {
+    get_user_id_by_login(login, &state.db).await
+        .ok_or_else(|| ApiError::not_found("No such player found."))
+}
+
+/// Checks admin has flag required by `permission_key` and returns their in-game name.
+async fn resolve_actor(admin: &Uuid, permission_key: &str, state: &ApiState) -> Result<SS14ApiActor, ApiError> {
+    let permissions = match load_permissions(admin, &state.db).await {
+        Ok(Some(permissions)) => permissions,
+        Ok(None) => return Err(ApiError::new(StatusCode::FORBIDDEN, "Actor is not an admin at SS14 server.")),
+        Err(e) => {
+            error!("Error loading admin permissions for {}: {}", admin, e);
+            return Err(ApiError::internal("Unable to load admin permissions."));
+        }
+    };
+
+    if let Some(flag) = required_flag(permission_key, &state.config) {
+        if !permissions.has_flag(&flag) {
+            return Err(ApiError::new(StatusCode::FORBIDDEN, &format!("Actor needs `{}` flag.", flag.to_uppercase())));
+        }
+    }
+
+    match state.db.get_login_by_uuid(admin).await {
+        Ok(Some(name)) => Ok(SS14ApiActor::new(*admin, &name)),
+        Ok(None) => Err(ApiError::not_found("Unable to fetch admin name.")),
+        Err(e) => {
+            warn!("Failed to fetch user name for {}: {}", admin, e);
+            Err(ApiError::internal("Unable to fetch admin name."))
+        }
+    }
+}
+
+/// Player of the ban for audit target, same as `/bans pardon` records.
+async fn ban_target(id: i32, state: &ApiState) -> String {
+    match state.db.get_ban_by_id(id).await {
+        Ok(Some(ban)) => resolve_optional_user_name(&state.db, ban.player_user_id.as_ref()).await,
+        _ => "Unknown".to_string(),
+    }
+}
+
+fn ss14_client(state: &ApiState) -> Result<SS14ApiClient, ApiError> {
+    SS14ApiClient::new(state.config.api_url(), state.config.server_token()).map_err(|e| {
+        error!("Error creating ss14 client: {e}");
+        ApiError::internal("Unable to setup ss14 client.")
+    })
+}
+
+fn ss14_error(e: Error) -> ApiError {
+    match e {
+        Error::SS14ApiError(e) => ApiError::new(StatusCode::BAD_GATEWAY, &e),
+        _ => ApiError::internal("Error occured at SS14 api."),
+    }
+}
+
+pub async fn whitelist_add(State(state): State<Arc<ApiState>>, Path(login): Path<String>, Json(body): Json<WhitelistBody>) -> ApiResult<StatusResponse> {
+    let actor = resolve_actor(&body.admin, "whitelist.add", &state).await?;
+    let uuid = resolve_login(&login, &state).await?;
+
+    match state.db.whitelistadd(&uuid).await {
+        Ok(_) => {
+            let audit = AuditEntry::api("whitelist.add", &login).admin(body.admin, &actor.name);
+            audit::record(audit, &state.http, &state.db, &state.config).await;
+            Ok(StatusResponse::new(format!("Successfully added {} to whitelist.", login)))
+        },
+        Err(Error::SqlxError(e)) if e.as_database_error().is_some_and(|e| e.is_unique_violation()) => {
+            Err(ApiError::new(StatusCode::CONFLICT, "Such player is already whitelisted."))
+        },
+        Err(e) => {
+            error!("Error adding player to whitelist: {}", e);
+            Err(ApiError::internal(&format!("Unable to add {} to whitelist.", login)))
+        }
+    }
+}
+
+pub async fn whitelist_rm(State(state): State<Arc<ApiState>>, Path(login): Path<String>, Json(body): Json<WhitelistBody>) -> ApiResult<StatusResponse> {
+    let actor = resolve_actor(&body.admin, "whitelist.rm", &state).await?;
+    let uuid = resolve_login(&login, &state).await?;
+
+    match state.db.whitelistrm(&uuid).await {
+        Ok(0) => Err(ApiError::not_found(&format!("User {} is not whitelisted.", login))),
+        Ok(_) => {
+            let audit = AuditEntry::api("whitelist.rm", &login).admin(body.admin, &actor.name);
+            audit::record(audit, &state.http, &state.db, &state.config).await;
+            Ok(StatusResponse::new(format!("Successfully removed {} from whitelist.", login)))
+        },
+        Err(e) => {
+            error!("Error removing player from whitelist: {}", e);
+            Err(ApiError::internal(&format!("Unable to remove {} from whitelist.", login)))
+        }
+    }
+}
+
+pub async fn notes_list(State(state): State<Arc<ApiState>>, Query(query): Query<LoginQuery>) -> ApiResult<Vec<AdminNoteShort>> {
+    let uuid = resolve_login(&query.login, &state).await?;
+
+    state.db.get_notes_list(&uuid).await.map(Json).map_err(|e| {
+        error!("Error retrieving notes for {}: {}", query.login, e);
+        ApiError::internal("Failed to retrieve notes.")
+    })
+}
+
+pub async fn note_info(State(state): State<Arc<ApiState>>, Path(id): Path<i32>) -> ApiResult<AdminNote> {
+    match state.db.get_note_by_id(id).await {
+        Ok(Some(note)) => Ok(Json(note)),
+        Ok(None) => Err(ApiError::not_found(&format!("Note with ID `{}` not found.", id))),
+        Err(e) => {
+            error!("Error fetching note with ID {}: {}", id, e);
+            Err(ApiError::internal("Error occurred while fetching the note."))
+        }
+    }
+}
+
+pub async fn bans_list(State(state): State<Arc<ApiState>>, Query(query): Query<LoginQuery>) -> ApiResult<Vec<ServerBanShort>> {
+    let uuid = resolve_login(&query.login, &state).await?;
+
+    state.db.get_bans_list(&uuid).await.map(Json).map_err(|e| {
+        error!("Error retrieving bans for {}. Error {}", query.login, e);
+        ApiError::internal("Failed to retrieve bans.")
+    })
+}
+
+pub async fn ban_info(State(state): State<Arc<ApiState>>, Path(id): Path<i32>) -> ApiResult<ServerBan> {
+    match state.db.get_ban_by_id(id).await {
+        Ok(Some(ban)) => Ok(Json(ban)),
+        Ok(None) => Err(ApiError::not_found(&format!("Ban with id: {} is not found", id))),
+        Err(e) => {
+            error!("Error retrieving ban by id: {}. Error: {}", id, e);
+            Err(ApiError::internal("Error happened retrieving ban."))
+        }
+    }
+}
+
+pub async fn ban(State(state): State<Arc<ApiState>>, Json(body): Json<BanBody>) -> ApiResult<StatusResponse> {
+    if body.login.is_empty() {
+        return Err(ApiError::bad_request("Banning player couldn't be empty"));
+    }
+
+    let minutes = parse_duration(&body.duration).map_err(|e| ApiError::bad_request(&e))?;
+    let actor = resolve_actor(&body.admin, "bans.ban", &state).await?;
+    check_duration_limit(&body.admin, minutes, &state.db, &state.config).await
+        .map_err(|e| ApiError::new(StatusCode::FORBIDDEN, &e))?;
+    let banning_uuid = resolve_login(&body.login, &state).await?;
+
+    let reason = body.reason.unwrap_or("No reason provided".to_string());
+    let severity = body.severity.unwrap_or(2);
+    let audit = AuditEntry::api("bans.ban", &body.login)
+        .admin(body.admin, &actor.name)
+        .param("duration", format_duration(minutes))
+        .param("severity", severity)
+        .param("reason", &reason);
+
+    let ban_cmd = BanRequest::new(
+        body.login.to_owned(),
+        banning_uuid,
+        reason,
+        minutes as i32,
+        severity,
+        actor
+    );
+
+    ss14_client(&state)?.ban(ban_cmd).await.map_err(|e| {
+        error!("Unable to ban player: {}. Error: {e}", body.login);
+        ss14_error(e)
+    })?;
+    audit::record(audit, &state.http, &state.db, &state.config).await;
+
+    Ok(StatusResponse::new(format!("Successfully banned player: `{}`", body.login)))
+}
+
+pub async fn pardon(State(state): State<Arc<ApiState>>, Path(id): Path<i32>, Json(body): Json<PardonBody>) -> ApiResult<StatusResponse> {
+    let actor = resolve_actor(&body.admin, "bans.pardon", &state).await?;
+    let audit = AuditEntry::api("bans.pardon", ban_target(id, &state).await)
+        .admin(body.admin, &actor.name)
+        .param("ban_id", id);
+
+    ss14_client(&state)?.pardon(PardonRequest::new(id, actor)).await.map_err(|e| {
+        error!("Unable to pardon ban. Id: {id}. Error: {e}");
+        ss14_error(e)
+    })?;
+    audit::record(audit, &state.http, &state.db, &state.config).await;
+
+    Ok(StatusResponse::new(format!("Successfully pardoned ban with id: {}", id)))
+}
+
+pub async fn ahelp(State(state): State<Arc<ApiState>>, Json(body): Json<AhelpBody>) -> ApiResult<AhelpResponse> {
+    let channel = state.config.ahelp_channel()
+        .ok_or_else(|| ApiError::not_found("Ahelp relay is disabled."))?;
+
+    if body.message.trim().is_empty() {
+        return Err(ApiError::bad_request("Message couldn't be empty."));
+    }
+
+    let ahelp = AhelpMessage {
+        player: body.user_id,
+        sender: body.sender.unwrap_or_else(|| body.username.clone()),
+        username: body.username,
+        message: body.message,
+    };
+
+    match relay_to_discord(ahelp, channel, &state.http, &state.db).await {
+        Ok(thread) => Ok(Json(AhelpResponse { thread_id: thread.to_string() })),
+        Err(e) => {
+            error!("Error relaying ahelp of {}: {}", body.user_id, e);
+            Err(ApiError::internal("Unable to relay ahelp to Discord."))
+        }
+    }
+}
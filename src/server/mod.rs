@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use axum::{extract::{Request, State}, http::{header, StatusCode}, middleware::{self, Next}, response::{IntoResponse, Response}, routing::{get, post}, Json, Router};
+use log::info;
+use serde::Serialize;
+use serenity::all::Http;
+use subtle::ConstantTimeEq;
+
+use crate::{config::Config, database::PgDatabase, error::Error};
+
+pub mod handlers;
+
+pub struct ApiServer {
+    state: Arc<ApiState>,
+}
+
+pub struct ApiState {
+    pub config: Config,
+    pub db: PgDatabase,
+    pub http: Http, // discord REST client, gateway is up to the bot
+}
+
+impl ApiServer {
+    pub fn new(config: &Config, db: PgDatabase) -> Self {
+        let http = Http::new(config.token());
+        Self { state: Arc::new(ApiState { config: config.clone(), db, http }) }
+    }
+
+    pub async fn start(self) -> Result<(), Error> {
+        match self.state.config.host_token() {
+            None => {
+                info!("No api_token configured, API server is disabled.");
+                return Ok(());
+            },
+            // empty token would let anyone with an empty bearer in
+            Some("") => return Err(Error::ConfigErr("`api_token` is empty, refusing to start API server".to_string())),
+            Some(_) => {},
+        }
+
+        let host = self.state.config.host().to_owned();
+        let router = Self::router(self.state);
+
+        let listener = tokio::net::TcpListener::bind(&host).await?;
+        info!("API server is listening on {}", host);
+
+        axum::serve(listener, router).await?;
+        Ok(())
+    }
+
+    fn router(state: Arc<ApiState>) -> Router {
+        Router::new()
+            .route("/api/whitelist/{login}", post(handlers::whitelist_add).delete(handlers::whitelist_rm))
+            .route("/api/notes", get(handlers::notes_list))
+            .route("/api/notes/{id}", get(handlers::note_info))
+            .route("/api/bans", get(handlers::bans_list).post(handlers::ban))
+            .route("/api/bans/{id}", get(handlers::ban_info))
+            .route("/api/bans/{id}/pardon", post(handlers::pardon))
+            .route("/api/ahelp", post(handlers::ahelp))
+            .layer(middleware::from_fn_with_state(state.clone(), authorize))
+            .with_state(state)
+    }
+}
+
+async fn authorize(State(state): State<Arc<ApiState>>, request: Request, next: Next) -> Response {
+    let token = request.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let expected = state.config.host_token().unwrap_or_default();
+    match token {
+        Some(token) if !expected.is_empty() && bool::from(token.as_bytes().ct_eq(expected.as_bytes())) => next.run(request).await,
+        _ => ApiError::new(StatusCode::UNAUTHORIZED, "Invalid or missing API token").into_response(),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiError {
+    #[serde(skip)]
+    status: StatusCode,
+    error: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, error: &str) -> Self {
+        Self { status, error: error.to_string() }
+    }
+
+    pub fn not_found(error: &str) -> Self {
+        Self::new(StatusCode::NOT_FOUND, error)
+    }
+
+    pub fn bad_request(error: &str) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, error)
+    }
+
+    pub fn internal(error: &str) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(&self)).into_response()
+    }
+}